@@ -1,7 +1,32 @@
+use crate::i18n::{self, Language};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// 币安支持的全部K线周期
+pub const KLINE_INTERVALS: [&str; 16] = [
+    "1s", "1m", "3m", "5m", "15m", "30m", "1h", "2h", "4h", "6h", "8h", "12h", "1d", "3d", "1w", "1M",
+];
+
+/// K线周期的显示名称，如 `15m` -> `15分钟`；英文界面直接使用周期本身
+pub fn interval_label(interval: &str) -> String {
+    if i18n::current() == Language::En {
+        return interval.to_string();
+    }
+    let split = interval.len().saturating_sub(1);
+    let (count, unit) = interval.split_at(split);
+    let unit = match unit {
+        "s" => "秒",
+        "m" => "分钟",
+        "h" => "小时",
+        "d" => "天",
+        "w" => "周",
+        "M" => "月",
+        _ => return interval.to_string(),
+    };
+    format!("{}{}", count, unit)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TickerPrice {
     pub symbol: String,
@@ -11,6 +36,16 @@ pub struct TickerPrice {
     pub volume: String,
     pub high_24h: String,
     pub low_24h: String,
+    pub open_24h: String,
+    pub weighted_avg_price: String,
+    /// 以计价资产计的24小时成交额
+    pub quote_volume: String,
+    /// 24小时成交笔数
+    pub trade_count: u64,
+    pub bid_price: String,
+    pub bid_quantity: String,
+    pub ask_price: String,
+    pub ask_quantity: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -24,6 +59,51 @@ pub struct KlineData {
     pub close_time: i64,
 }
 
+/// 订单簿，档位为 (价格, 数量)，买盘按价格从高到低、卖盘从低到高排列
+#[derive(Debug, Default, Clone)]
+pub struct OrderBook {
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
+}
+
+impl OrderBook {
+    /// 买一与卖一的中间价，任一侧为空时返回 `None`
+    pub fn mid_price(&self) -> Option<f64> {
+        let (bid, _) = self.bids.first()?;
+        let (ask, _) = self.asks.first()?;
+        Some((bid + ask) / 2.0)
+    }
+}
+
+/// 交易对在 `exchangeInfo` 中的交易规则，价格步长（`tickSize`）和数量步长（`stepSize`）
+/// 决定显示精度，其余字段缺失时为默认值
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SymbolFilters {
+    pub tick_size: f64,
+    pub step_size: f64,
+    /// 交易状态，如 `TRADING`、`BREAK`
+    pub status: String,
+    pub base_asset: String,
+    pub quote_asset: String,
+    pub min_price: f64,
+    pub max_price: f64,
+    pub min_quantity: f64,
+    pub max_quantity: f64,
+    /// 单笔订单的最小成交额
+    pub min_notional: f64,
+}
+
+/// 一笔成交
+#[derive(Debug, Clone)]
+pub struct Trade {
+    pub price: f64,
+    pub quantity: f64,
+    /// 成交时间（毫秒）
+    pub time: i64,
+    /// 买方为挂单方，即主动卖出
+    pub is_buyer_maker: bool,
+}
+
 pub struct BinanceApi {
     base_url: String,
     client: reqwest::Client,
@@ -55,6 +135,14 @@ impl BinanceApi {
                     volume: ticker["volume"].as_str().unwrap_or("0").to_string(),
                     high_24h: ticker["highPrice"].as_str().unwrap_or("0").to_string(),
                     low_24h: ticker["lowPrice"].as_str().unwrap_or("0").to_string(),
+                    open_24h: ticker["openPrice"].as_str().unwrap_or("0").to_string(),
+                    weighted_avg_price: ticker["weightedAvgPrice"].as_str().unwrap_or("0").to_string(),
+                    quote_volume: ticker["quoteVolume"].as_str().unwrap_or("0").to_string(),
+                    trade_count: ticker["count"].as_u64().unwrap_or(0),
+                    bid_price: ticker["bidPrice"].as_str().unwrap_or("0").to_string(),
+                    bid_quantity: ticker["bidQty"].as_str().unwrap_or("0").to_string(),
+                    ask_price: ticker["askPrice"].as_str().unwrap_or("0").to_string(),
+                    ask_quantity: ticker["askQty"].as_str().unwrap_or("0").to_string(),
                 };
                 
                 prices.insert(symbol.clone(), price);
@@ -69,8 +157,161 @@ impl BinanceApi {
             "{}/api/v3/klines?symbol={}&interval={}&limit={}",
             self.base_url, symbol, interval, limit
         );
-        
-        let response = self.client.get(&url).send().await?;
+        self.fetch_klines(&url).await
+    }
+
+    /// 获取 `end_time`（毫秒，含）之前的K线，用于向前翻页加载历史
+    pub async fn get_klines_before(
+        &self,
+        symbol: &str,
+        interval: &str,
+        end_time: i64,
+        limit: u32,
+    ) -> Result<Vec<KlineData>> {
+        let url = format!(
+            "{}/api/v3/klines?symbol={}&interval={}&limit={}&endTime={}",
+            self.base_url, symbol, interval, limit, end_time
+        );
+        self.fetch_klines(&url).await
+    }
+
+    /// 获取开盘时间在 `start_time` 到 `end_time`（毫秒，含）之间的K线，用于与其他交易对对齐。
+    /// 每次请求最多 `limit` 根，超过时按 startTime 向后分页直到覆盖整个区间。
+    pub async fn get_klines_between(
+        &self,
+        symbol: &str,
+        interval: &str,
+        start_time: i64,
+        end_time: i64,
+        limit: u32,
+    ) -> Result<Vec<KlineData>> {
+        let mut klines: Vec<KlineData> = Vec::new();
+        let mut start_time = start_time;
+        loop {
+            let url = format!(
+                "{}/api/v3/klines?symbol={}&interval={}&limit={}&startTime={}&endTime={}",
+                self.base_url, symbol, interval, limit, start_time, end_time
+            );
+            let page = self.fetch_klines(&url).await?;
+            let full = page.len() >= limit as usize;
+            klines.extend(page);
+            match klines.last() {
+                Some(last) if full && last.open_time < end_time => start_time = last.open_time + 1,
+                _ => return Ok(klines),
+            }
+        }
+    }
+
+    /// 获取订单簿的前 `limit` 档挂单
+    pub async fn get_order_book(&self, symbol: &str, limit: u32) -> Result<OrderBook> {
+        let url = format!("{}/api/v3/depth?symbol={}&limit={}", self.base_url, symbol, limit);
+        let response = self.client.get(&url).send().await?.error_for_status()?;
+        let depth: serde_json::Value = response.json().await?;
+
+        let levels = |side: &serde_json::Value| -> Vec<(f64, f64)> {
+            side.as_array()
+                .map(|levels| {
+                    levels
+                        .iter()
+                        .filter_map(|level| {
+                            let price = level[0].as_str()?.parse().ok()?;
+                            let quantity = level[1].as_str()?.parse().ok()?;
+                            Some((price, quantity))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        Ok(OrderBook {
+            bids: levels(&depth["bids"]),
+            asks: levels(&depth["asks"]),
+        })
+    }
+
+    /// 获取最近的 `limit` 笔成交，按时间从早到晚排列
+    pub async fn get_recent_trades(&self, symbol: &str, limit: u32) -> Result<Vec<Trade>> {
+        let url = format!("{}/api/v3/trades?symbol={}&limit={}", self.base_url, symbol, limit);
+        let response = self.client.get(&url).send().await?.error_for_status()?;
+        let trades: Vec<serde_json::Value> = response.json().await?;
+
+        Ok(trades
+            .iter()
+            .filter_map(|trade| {
+                Some(Trade {
+                    price: trade["price"].as_str()?.parse().ok()?,
+                    quantity: trade["qty"].as_str()?.parse().ok()?,
+                    time: trade["time"].as_i64()?,
+                    is_buyer_maker: trade["isBuyerMaker"].as_bool().unwrap_or(false),
+                })
+            })
+            .collect())
+    }
+
+    /// 一次获取多个交易对的交易规则，缺少相应过滤器的交易对不在结果中。
+    /// 其中有无效交易对时整个请求会被拒绝，此时改为逐个请求，只跳过无效的交易对。
+    pub async fn get_symbol_filters(&self, symbols: &[String]) -> Result<HashMap<String, SymbolFilters>> {
+        match self.fetch_symbol_filters(symbols).await {
+            Err(e) if symbols.len() > 1 && is_client_error(&e) => {}
+            result => return result,
+        }
+        let mut result = HashMap::new();
+        for symbol in symbols {
+            match self.fetch_symbol_filters(std::slice::from_ref(symbol)).await {
+                Ok(filters) => result.extend(filters),
+                Err(e) if is_client_error(&e) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(result)
+    }
+
+    async fn fetch_symbol_filters(&self, symbols: &[String]) -> Result<HashMap<String, SymbolFilters>> {
+        let url = format!("{}/api/v3/exchangeInfo", self.base_url);
+        let symbols = serde_json::to_string(symbols)?;
+        let response = self.client.get(&url).query(&[("symbols", symbols)]).send().await?;
+        let info: serde_json::Value = response.error_for_status()?.json().await?;
+
+        let mut result = HashMap::new();
+        for symbol in info["symbols"].as_array().into_iter().flatten() {
+            let filter = |kind: &str, field: &str| -> Option<f64> {
+                symbol["filters"]
+                    .as_array()?
+                    .iter()
+                    .find(|filter| filter["filterType"] == kind)?[field]
+                    .as_str()?
+                    .parse()
+                    .ok()
+            };
+            let (Some(name), Some(tick_size), Some(step_size)) = (
+                symbol["symbol"].as_str(),
+                filter("PRICE_FILTER", "tickSize"),
+                filter("LOT_SIZE", "stepSize"),
+            ) else {
+                continue;
+            };
+            let text = |field: &str| symbol[field].as_str().unwrap_or_default().to_string();
+            let filters = SymbolFilters {
+                tick_size,
+                step_size,
+                status: text("status"),
+                base_asset: text("baseAsset"),
+                quote_asset: text("quoteAsset"),
+                min_price: filter("PRICE_FILTER", "minPrice").unwrap_or_default(),
+                max_price: filter("PRICE_FILTER", "maxPrice").unwrap_or_default(),
+                min_quantity: filter("LOT_SIZE", "minQty").unwrap_or_default(),
+                max_quantity: filter("LOT_SIZE", "maxQty").unwrap_or_default(),
+                // 新的交易对使用 NOTIONAL，部分旧交易对仍为 MIN_NOTIONAL
+                min_notional: filter("NOTIONAL", "minNotional")
+                    .or_else(|| filter("MIN_NOTIONAL", "minNotional"))
+                    .unwrap_or_default(),
+            };
+            result.insert(name.to_string(), filters);
+        }
+        Ok(result)
+    }
+
+    async fn fetch_klines(&self, url: &str) -> Result<Vec<KlineData>> {
+        let response = self.client.get(url).send().await?;
         let klines: Vec<Vec<serde_json::Value>> = response.json().await?;
         
         let mut result = Vec::new();
@@ -90,4 +331,12 @@ impl BinanceApi {
         
         Ok(result)
     }
-} 
\ No newline at end of file
+}
+
+/// 请求被币安拒绝（4xx），例如交易对不存在
+fn is_client_error(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<reqwest::Error>()
+        .and_then(reqwest::Error::status)
+        .is_some_and(|status| status.is_client_error())
+}
@@ -1,25 +1,198 @@
 use crate::{
-    api::{BinanceApi, KlineData, TickerPrice},
-    config::AppConfig,
+    api::{BinanceApi, KlineData, OrderBook, SymbolFilters, TickerPrice, Trade, KLINE_INTERVALS},
+    command::{self, Command},
+    config::{AppConfig, PriceColors, ThemePreset, GRID_SIZES},
+    i18n::{self, tr, trf},
+    keymap::{Action, KeyMap},
+    theme::Theme,
 };
 use anyhow::Result;
-use std::collections::HashMap;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{layout::Rect, widgets::TableState};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 use std::time::{Duration, Instant};
 
+/// 缩放时最少显示的K线数量
+const MIN_VISIBLE_CANDLES: usize = 10;
+/// 图表宽度未知时（首次绘制前）请求的K线数量
+const DEFAULT_CANDLE_LIMIT: u32 = 100;
+/// 单次请求K线数量的上限（币安接口限制）
+const MAX_CANDLE_LIMIT: u32 = 1000;
+/// 每个交易对最多保留的K线数量，超出时丢弃最早的部分
+const MAX_LOADED_CANDLES: usize = 20_000;
+/// 命令历史最多保留的条数
+const MAX_COMMAND_HISTORY: usize = 100;
+/// 详情页显示的最近成交笔数
+const RECENT_TRADES_LIMIT: u32 = 50;
+
 #[derive(Debug, Clone)]
 pub enum InputMode {
     Normal,
     AddingPair,
+    Command,
+    /// 显示按键和命令帮助
+    Help,
+}
+
+/// Tab 连续按下时在多个补全候选间循环
+#[derive(Debug, Clone)]
+struct Completion {
+    base: String,
+    candidates: Vec<String>,
+    index: usize,
+}
+
+/// 上一帧绘制时各区域的位置，用于鼠标命中检测
+#[derive(Debug, Default, Clone)]
+pub struct LayoutAreas {
+    pub symbol_table: Rect,
+    /// 当前交易对的图表区域，网格模式下为获得焦点的格子
+    pub chart: Rect,
+    /// 网格模式下各格子的区域
+    pub grid_cells: Vec<Rect>,
+    /// 图表中实际绘制K线的区域（不含边框和价格轴）
+    pub chart_plot: Rect,
+}
+
+/// K线图的可视窗口：显示多少根K线，以及距最新K线的偏移
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ChartView {
+    /// 可见K线数量，`None` 表示显示全部已加载的K线
+    pub visible: Option<usize>,
+    /// 窗口右端距离最新一根K线的数量
+    pub offset: usize,
+    /// 绘图区域的列数，每列最多显示一根K线，`None` 表示不限制
+    pub max_visible: Option<usize>,
+}
+
+impl ChartView {
+    /// 根据已加载的K线总数计算可见区间
+    pub fn window(&self, total: usize) -> Range<usize> {
+        let visible = self.visible_count(total);
+        let end = total - self.offset.min(total - visible);
+        end - visible..end
+    }
+
+    /// `factor < 1.0` 放大（显示更少K线），`factor > 1.0` 缩小。
+    /// 返回是否需要加载更早的历史才能满足缩小请求。
+    pub fn zoom(&mut self, factor: f64, total: usize) -> bool {
+        if total == 0 {
+            return false;
+        }
+        let current = self.visible_count(total);
+        let requested = (current as f64 * factor).round() as usize;
+        // 缩小到每列一根K线后不再继续缩小，否则多根K线挤在同一列会丢失最高、最低价
+        let limit = self.max_visible.map_or(usize::MAX, |max| max.max(1));
+        let next = requested.min(limit).clamp(MIN_VISIBLE_CANDLES.min(total), total);
+        // 缩放后保持窗口右端位置不变
+        self.visible = Some(next);
+        self.offset = self.offset.min(total - next);
+        requested.min(limit) > total
+    }
+
+    /// `delta > 0` 向更早的历史平移，`delta < 0` 向最新平移。
+    /// 返回是否已到达已加载数据的最早端、需要加载更早的历史。
+    pub fn pan(&mut self, delta: isize, total: usize) -> bool {
+        let visible = self.visible_count(total);
+        let max_offset = (total - visible) as isize;
+        let requested = self.offset as isize + delta;
+        self.offset = requested.clamp(0, max_offset) as usize;
+        delta > 0 && requested >= max_offset
+    }
+
+    pub fn jump_to_latest(&mut self) {
+        self.offset = 0;
+    }
+
+    fn visible_count(&self, total: usize) -> usize {
+        let visible = self.visible.unwrap_or(total).min(total);
+        self.max_visible.map_or(visible, |max| visible.min(max.max(1)))
+    }
+}
+
+/// 交易对列表中迷你走势图的数据：最近24小时的1小时收盘价
+pub struct Sparkline {
+    pub closes: Vec<f64>,
+    fetched_at: Instant,
+}
+
+const SPARKLINE_INTERVAL: &str = "1h";
+const SPARKLINE_POINTS: u32 = 24;
+/// 走势图数据的缓存时间，过期后在下次刷新时重新获取
+const SPARKLINE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// 最新价相对上次刷新上涨或下跌时，价格单元格短暂闪烁
+#[derive(Debug, Clone, Copy)]
+pub struct PriceFlash {
+    pub rising: bool,
+    started: Instant,
+}
+
+/// 对比数据对应的请求，可见窗口或对比列表变化后需要重新获取
+#[derive(Debug, Clone, PartialEq)]
+struct ComparisonKey {
+    symbol: String,
+    interval: String,
+    start: i64,
+    end: i64,
+    compare: Vec<String>,
+}
+
+/// 鼠标在K线图上拖动时的起点
+#[derive(Debug, Clone, Copy)]
+struct ChartDrag {
+    column: u16,
+    offset: usize,
 }
 
 pub struct App {
     pub config: AppConfig,
     pub api: BinanceApi,
     pub input_mode: InputMode,
+    pub keymap: KeyMap,
+    pub theme: Theme,
     pub input_buffer: String,
+    /// 帮助窗口向下滚动的行数
+    pub help_scroll: u16,
+    pub command_history: Vec<String>,
+    history_index: Option<usize>,
+    completion: Option<Completion>,
     pub ticker_prices: HashMap<String, TickerPrice>,
     pub kline_data: HashMap<String, Vec<KlineData>>,
+    pub sparklines: HashMap<String, Sparkline>,
+    pub price_flashes: HashMap<String, PriceFlash>,
+    /// 各交易对的价格和数量精度，获取一次后不再更新
+    pub symbol_filters: HashMap<String, SymbolFilters>,
+    /// 对比交易对在当前可见窗口内的K线
+    pub comparison: HashMap<String, Vec<KlineData>>,
+    comparison_key: Option<ComparisonKey>,
+    /// 深度图使用的订单簿
+    pub order_books: HashMap<String, OrderBook>,
+    /// 本次刷新周期内已请求过订单簿的交易对
+    order_book_symbol: Option<String>,
+    /// 详情页中的最近成交，按时间从早到晚排列
+    pub recent_trades: HashMap<String, Vec<Trade>>,
+    /// 本次刷新周期内已请求过最近成交的交易对
+    trades_symbol: Option<String>,
+    /// 是否显示选中交易对的全屏详情页
+    pub detail_view: bool,
     pub selected_symbol: Option<String>,
+    pub status_message: Option<String>,
+    pub chart_view: ChartView,
+    /// 十字光标所在K线距最新K线的数量，`None` 表示未开启光标模式
+    pub crosshair: Option<usize>,
+    /// 网格模式下获得焦点的格子，其交易对即当前选中的交易对
+    pub grid_focus: usize,
+    /// 平移或缩放到了已加载数据的最早端，等待加载更早的K线
+    history_requested: bool,
+    /// 已经没有更早K线可加载的交易对
+    history_exhausted: HashSet<String>,
+    /// 币安没有返回交易规则的交易对（如无效的交易对），不在每次刷新时重复请求
+    filters_unavailable: HashSet<String>,
+    pub table_state: TableState,
+    pub layout: LayoutAreas,
+    chart_drag: Option<ChartDrag>,
     pub last_refresh: Instant,
     pub should_quit: bool,
 }
@@ -27,15 +200,60 @@ pub struct App {
 impl App {
     pub fn new(config: AppConfig) -> Self {
         let api = BinanceApi::new(config.binance_api_url.clone());
+        let (keymap, key_warnings) = KeyMap::from_config(&config.keybindings);
+        let (theme, theme_warnings) = Theme::from_config(&config.theme);
+        let mut save_warnings = Vec::new();
+        if config.needs_save {
+            if let Err(e) = config.save() {
+                save_warnings.push(trf("保存迁移后的配置失败: {}", &[&e]));
+            }
+        }
+        // 按键、配色和配置文件的问题在启动时显示在状态栏
+        let warnings: Vec<String> = [
+            (tr("按键配置"), key_warnings),
+            (tr("主题配置"), theme_warnings),
+            (tr("配置文件"), save_warnings),
+        ]
+            .into_iter()
+            .filter(|(_, warnings)| !warnings.is_empty())
+            .map(|(label, warnings)| format!("{}: {}", label, warnings.join("; ")))
+            .collect();
+        let status_message = (!warnings.is_empty()).then(|| warnings.join(" | "));
         
         Self {
             config,
             api,
             input_mode: InputMode::Normal,
+            keymap,
+            theme,
             input_buffer: String::new(),
+            help_scroll: 0,
+            command_history: Vec::new(),
+            history_index: None,
+            completion: None,
             ticker_prices: HashMap::new(),
             kline_data: HashMap::new(),
+            sparklines: HashMap::new(),
+            symbol_filters: HashMap::new(),
+            price_flashes: HashMap::new(),
+            comparison: HashMap::new(),
+            comparison_key: None,
+            order_books: HashMap::new(),
+            order_book_symbol: None,
+            recent_trades: HashMap::new(),
+            trades_symbol: None,
+            detail_view: false,
             selected_symbol: None,
+            status_message,
+            chart_view: ChartView::default(),
+            crosshair: None,
+            grid_focus: 0,
+            history_requested: false,
+            history_exhausted: HashSet::new(),
+            filters_unavailable: HashSet::new(),
+            table_state: TableState::default(),
+            layout: LayoutAreas::default(),
+            chart_drag: None,
             last_refresh: Instant::now(),
             should_quit: false,
         }
@@ -46,20 +264,791 @@ impl App {
         
         // 获取价格数据
         let prices = self.api.get_ticker_prices(&symbols).await?;
+        self.start_price_flashes(&prices);
         self.ticker_prices.extend(prices);
+        self.refresh_sparklines(&symbols).await;
+        self.load_symbol_filters(&symbols).await;
         
-        // 获取K线数据 (5分钟周期)
+        // 获取K线数据
         for symbol in &symbols {
-            let klines = self.api.get_klines(symbol, "5m", 100).await?;
-            self.kline_data.insert(symbol.clone(), klines);
+            self.refresh_klines(symbol).await?;
         }
         
         self.last_refresh = Instant::now();
+        // 最新K线已更新，对比数据、订单簿和最近成交随之重新获取
+        self.comparison_key = None;
+        self.order_book_symbol = None;
+        self.trades_symbol = None;
+        self.check_alerts();
+        Ok(())
+    }
+
+    /// 与上次的最新价比较，价格变化的交易对开始闪烁
+    fn start_price_flashes(&mut self, prices: &HashMap<String, TickerPrice>) {
+        if self.config.price_flash_ms == 0 {
+            return;
+        }
+        for (symbol, price) in prices {
+            let Some(previous) = self.ticker_prices.get(symbol) else {
+                continue;
+            };
+            let (Ok(current), Ok(previous)) = (price.price.parse::<f64>(), previous.price.parse::<f64>()) else {
+                continue;
+            };
+            if current != previous {
+                self.price_flashes.insert(
+                    symbol.clone(),
+                    PriceFlash {
+                        rising: current > previous,
+                        started: Instant::now(),
+                    },
+                );
+            }
+        }
+    }
+
+    /// 闪烁的方向和剩余比例（刚开始时为 1，逐渐减小到 0），没有闪烁时返回 `None`
+    pub fn price_flash(&self, symbol: &str) -> Option<(bool, f64)> {
+        let flash = self.price_flashes.get(symbol)?;
+        let duration = Duration::from_millis(self.config.price_flash_ms);
+        let remaining = 1.0 - flash.started.elapsed().as_secs_f64() / duration.as_secs_f64();
+        (remaining > 0.0).then_some((flash.rising, remaining))
+    }
+
+    /// 移除已经结束的闪烁，由定时事件调用，使闪烁随之逐渐消退
+    pub fn expire_price_flashes(&mut self) {
+        let duration = Duration::from_millis(self.config.price_flash_ms);
+        self.price_flashes.retain(|_, flash| flash.started.elapsed() < duration);
+    }
+
+    /// 获取缓存已过期的走势图数据。走势图只是辅助信息，
+    /// 获取失败时保留旧数据，不影响其余数据的刷新。
+    async fn refresh_sparklines(&mut self, symbols: &[String]) {
+        for symbol in symbols {
+            if self
+                .sparklines
+                .get(symbol)
+                .is_some_and(|sparkline| sparkline.fetched_at.elapsed() < SPARKLINE_TTL)
+            {
+                continue;
+            }
+            if let Ok(klines) = self.api.get_klines(symbol, SPARKLINE_INTERVAL, SPARKLINE_POINTS).await {
+                let closes = klines
+                    .iter()
+                    .map(|k| k.close.parse::<f64>().unwrap_or(0.0))
+                    .collect();
+                self.sparklines.insert(
+                    symbol.clone(),
+                    Sparkline {
+                        closes,
+                        fetched_at: Instant::now(),
+                    },
+                );
+            }
+        }
+    }
+
+    /// 获取尚未缓存的交易对精度。获取失败时按数值本身的位数显示，网络错误时下次刷新重试，
+    /// 币安没有返回规则的交易对则不再请求。
+    async fn load_symbol_filters(&mut self, symbols: &[String]) {
+        let missing: Vec<String> = symbols
+            .iter()
+            .filter(|symbol| !self.symbol_filters.contains_key(*symbol) && !self.filters_unavailable.contains(*symbol))
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+        if let Ok(filters) = self.api.get_symbol_filters(&missing).await {
+            self.filters_unavailable
+                .extend(missing.into_iter().filter(|symbol| !filters.contains_key(symbol)));
+            self.symbol_filters.extend(filters);
+        }
+    }
+
+    /// 按交易对自己的周期获取最新K线，数量与图表宽度一致（每列一根K线）。
+    /// 新数据会合并到已加载的历史中，而不是替换掉它。
+    pub async fn refresh_klines(&mut self, symbol: &str) -> Result<()> {
+        let interval = self.config.interval_for(symbol).to_string();
+        let latest = self.api.get_klines(symbol, &interval, self.candle_limit()).await?;
+        let Some(first_open) = latest.first().map(|k| k.open_time) else {
+            return Ok(());
+        };
+
+        let klines = self.kline_data.entry(symbol.to_string()).or_default();
+        let previous_last = klines.last().map(|k| k.open_time);
+        // 与新数据重叠的部分（包括尚未收盘的最后一根）以新数据为准
+        match previous_last {
+            Some(last) if last >= first_open => {
+                let keep = klines.partition_point(|k| k.open_time < first_open);
+                klines.truncate(keep);
+            }
+            // 已加载的历史与最新数据之间没有重叠（如长时间未刷新），直接替换
+            _ => klines.clear(),
+        }
+        klines.extend(latest);
+
+        // 查看历史时保持画面和光标停留在原来的K线上
+        if self.selected_symbol.as_deref() == Some(symbol) {
+            let added = klines
+                .iter()
+                .rev()
+                .take_while(|k| previous_last.is_some_and(|last| k.open_time > last))
+                .count();
+            if self.chart_view.offset > 0 {
+                self.chart_view.offset += added;
+            }
+            if let Some(crosshair) = &mut self.crosshair {
+                *crosshair += added;
+            }
+        }
         Ok(())
     }
 
+    /// 在平移或缩放触及最早端时，为选中的交易对向前加载一页历史K线
+    pub async fn load_requested_history(&mut self) {
+        if !std::mem::take(&mut self.history_requested) {
+            return;
+        }
+        let Some(symbol) = self.selected_symbol.clone() else {
+            return;
+        };
+        if self.history_exhausted.contains(&symbol) {
+            return;
+        }
+        let Some(first_open) = self
+            .kline_data
+            .get(&symbol)
+            .and_then(|klines| klines.first())
+            .map(|k| k.open_time)
+        else {
+            return;
+        };
+
+        let interval = self.config.interval_for(&symbol).to_string();
+        let older = match self
+            .api
+            .get_klines_before(&symbol, &interval, first_open - 1, MAX_CANDLE_LIMIT)
+            .await
+        {
+            Ok(older) => older,
+            Err(e) => {
+                self.set_status(trf("加载历史K线失败: {}", &[&e]));
+                return;
+            }
+        };
+        if older.is_empty() {
+            self.history_exhausted.insert(symbol);
+            self.set_status(tr("已到达最早的K线"));
+            return;
+        }
+
+        let klines = self.kline_data.entry(symbol).or_default();
+        // 显示全部时固定当前可见数量，避免加载后画面突然缩小
+        if self.chart_view.visible.is_none() {
+            self.chart_view.visible = Some(klines.len());
+        }
+        let mut merged = older;
+        merged.retain(|k| k.open_time < first_open);
+        merged.append(klines);
+        if merged.len() > MAX_LOADED_CANDLES {
+            merged.drain(..merged.len() - MAX_LOADED_CANDLES);
+        }
+        *klines = merged;
+    }
+
+    /// 开启对比时，按选中交易对的周期和可见窗口获取对比交易对的K线。
+    /// 窗口或对比列表没有变化时不重复请求。
+    pub async fn load_comparison(&mut self) {
+        let Some(symbol) = self.selected_symbol.clone() else {
+            return;
+        };
+        let compare: Vec<String> = self
+            .config
+            .chart
+            .compare_symbols
+            .iter()
+            .filter(|other| **other != symbol)
+            .cloned()
+            .collect();
+        let visible = self.kline_data.get(&symbol).and_then(|klines| {
+            let window = self.chart_view.window(klines.len());
+            Some((klines.get(window.start)?.open_time, klines.get(window.end.checked_sub(1)?)?.open_time))
+        });
+        let (Some((start, end)), false) = (visible, compare.is_empty()) else {
+            self.comparison.clear();
+            self.comparison_key = None;
+            return;
+        };
+
+        let key = ComparisonKey {
+            interval: self.config.interval_for(&symbol).to_string(),
+            symbol,
+            start,
+            end,
+            compare,
+        };
+        if self.comparison_key.as_ref() == Some(&key) {
+            return;
+        }
+        // 先记录请求，失败时不在每次事件中重试，等下次刷新
+        self.comparison_key = Some(key.clone());
+        self.comparison.clear();
+        for other in &key.compare {
+            match self
+                .api
+                .get_klines_between(other, &key.interval, key.start, key.end, MAX_CANDLE_LIMIT)
+                .await
+            {
+                Ok(klines) => {
+                    self.comparison.insert(other.clone(), klines);
+                }
+                Err(e) => self.set_status(trf("获取 {} 对比数据失败: {}", &[other, &e])),
+            }
+        }
+    }
+
+    /// 显示深度图或详情页时获取选中交易对的订单簿，每个刷新周期只请求一次
+    pub async fn load_order_book(&mut self) {
+        if !self.config.depth.show && !self.detail_view {
+            return;
+        }
+        let Some(symbol) = self.selected_symbol.clone() else {
+            return;
+        };
+        if self.order_book_symbol.as_ref() == Some(&symbol) {
+            return;
+        }
+        self.order_book_symbol = Some(symbol.clone());
+        match self.api.get_order_book(&symbol, self.config.depth.limit).await {
+            Ok(book) => {
+                self.order_books.insert(symbol, book);
+            }
+            Err(e) => self.set_status(trf("获取 {} 订单簿失败: {}", &[&symbol, &e])),
+        }
+    }
+
+    /// 详情页打开时获取选中交易对的最近成交，每个刷新周期只请求一次
+    pub async fn load_recent_trades(&mut self) {
+        if !self.detail_view {
+            return;
+        }
+        let Some(symbol) = self.selected_symbol.clone() else {
+            return;
+        };
+        if self.trades_symbol.as_ref() == Some(&symbol) {
+            return;
+        }
+        self.trades_symbol = Some(symbol.clone());
+        match self.api.get_recent_trades(&symbol, RECENT_TRADES_LIMIT).await {
+            Ok(trades) => {
+                self.recent_trades.insert(symbol, trades);
+            }
+            Err(e) => self.set_status(trf("获取 {} 最近成交失败: {}", &[&symbol, &e])),
+        }
+    }
+
+    /// 平移K线图，`delta > 0` 查看更早的K线
+    pub fn pan_chart(&mut self, delta: isize) {
+        let total = self.loaded_candles();
+        if self.chart_view.pan(delta, total) {
+            self.history_requested = true;
+        }
+    }
+
+    /// 缩放K线图，`factor > 1.0` 显示更多K线
+    pub fn zoom_chart(&mut self, factor: f64) {
+        let total = self.loaded_candles();
+        if self.chart_view.zoom(factor, total) {
+            self.history_requested = true;
+        }
+    }
+
+    /// 记录绘制后的K线绘图区域，可见K线数量不超过其列数
+    pub fn set_chart_plot(&mut self, plot: Rect) {
+        self.layout.chart_plot = plot;
+        self.chart_view.max_visible = (plot.width > 0).then_some(plot.width as usize);
+    }
+
+    /// 开启或关闭十字光标，开启时光标位于最右侧可见的K线
+    pub fn toggle_crosshair(&mut self) {
+        self.crosshair = match self.crosshair {
+            Some(_) => None,
+            None => (self.loaded_candles() > 0).then_some(self.chart_view.offset),
+        };
+    }
+
+    /// 十字光标在已加载K线中的下标
+    pub fn crosshair_index(&self, total: usize) -> Option<usize> {
+        self.crosshair
+            .filter(|from_latest| *from_latest < total)
+            .map(|from_latest| total - 1 - from_latest)
+    }
+
+    /// 逐根移动十字光标，`delta > 0` 向更早的K线移动，移出可见范围时平移图表
+    fn move_crosshair(&mut self, delta: isize) {
+        let total = self.loaded_candles();
+        let Some(current) = self.crosshair else {
+            return;
+        };
+        let next = (current as isize + delta).clamp(0, total.saturating_sub(1) as isize) as usize;
+        self.crosshair = Some(next);
+
+        let window = self.chart_view.window(total);
+        let index = total - 1 - next;
+        if index < window.start {
+            self.pan_chart((window.start - index) as isize);
+        } else if index >= window.end {
+            self.pan_chart(-((index + 1 - window.end) as isize));
+        }
+    }
+
+    /// 调整成交量区域占图表高度的百分比
+    fn resize_volume(&mut self, delta: i16) {
+        let chart = &mut self.config.chart;
+        chart.show_volume = true;
+        chart.volume_height_percent = (chart.volume_height_percent as i16 + delta).clamp(10, 60) as u16;
+        self.persist_config();
+    }
+
+    /// 每次平移的K线数量：可见数量的十分之一，至少一根
+    fn pan_step(&self) -> isize {
+        let visible = self.chart_view.window(self.loaded_candles()).len();
+        (visible / 10).max(1) as isize
+    }
+
+    fn candle_limit(&self) -> u32 {
+        match self.layout.chart.width.saturating_sub(2) {
+            0 => DEFAULT_CANDLE_LIMIT,
+            width => (width as u32).min(MAX_CANDLE_LIMIT),
+        }
+    }
+
+    /// 当前选中交易对的K线周期，未选中时为默认周期
+    pub fn current_interval(&self) -> &str {
+        match &self.selected_symbol {
+            Some(symbol) => self.config.interval_for(symbol),
+            None => self.config.global_interval(),
+        }
+    }
+
+    /// 设置选中交易对的K线周期并重新加载其K线，未选中时修改默认周期
+    pub async fn set_interval(&mut self, interval: &str) {
+        let Some(symbol) = self.selected_symbol.clone() else {
+            self.config.default_interval = interval.to_string();
+            self.kline_data.clear();
+            self.history_exhausted.clear();
+            self.persist_config();
+            if let Err(e) = self.refresh_data().await {
+                self.set_status(trf("刷新数据失败: {}", &[&e]));
+            }
+            return;
+        };
+
+        self.config.set_interval(&symbol, interval);
+        self.chart_view = ChartView::default();
+        self.crosshair = None;
+        self.kline_data.remove(&symbol);
+        self.history_exhausted.remove(&symbol);
+        self.set_status(trf("{} K线周期: {}", &[&symbol, &interval]));
+        self.persist_config();
+        if let Err(e) = self.refresh_klines(&symbol).await {
+            self.set_status(trf("刷新数据失败: {}", &[&e]));
+        }
+    }
+
+    /// 在全部周期中前后切换
+    async fn cycle_interval(&mut self, delta: isize) {
+        let current = self.current_interval();
+        let index = KLINE_INTERVALS.iter().position(|i| *i == current).unwrap_or(0) as isize;
+        let next = (index + delta).clamp(0, KLINE_INTERVALS.len() as isize - 1) as usize;
+        if KLINE_INTERVALS[next] != current {
+            self.set_interval(KLINE_INTERVALS[next]).await;
+        }
+    }
+
+    /// 切换当前交易对的图表样式并保存
+    fn cycle_chart_style(&mut self) {
+        let Some(symbol) = self.selected_symbol.clone() else {
+            return;
+        };
+        let style = self.config.chart_style_for(&symbol).next();
+        self.config.set_chart_style(&symbol, style);
+        self.set_status(trf("{} 图表样式: {}", &[&symbol, &style.label()]));
+        self.persist_config();
+    }
+
+    /// 检查价格提醒，已触发的提醒显示在状态栏并移除
+    fn check_alerts(&mut self) {
+        let mut triggered = Vec::new();
+        self.config.alerts.retain(|alert| {
+            let price = self
+                .ticker_prices
+                .get(&alert.symbol)
+                .and_then(|ticker| ticker.price.parse::<f64>().ok());
+            match price {
+                Some(price) if alert.is_triggered(price) => {
+                    triggered.push(trf("{} (当前 {})", &[alert, &price]));
+                    false
+                }
+                _ => true,
+            }
+        });
+
+        if !triggered.is_empty() {
+            self.set_status(trf("价格提醒: {}", &[&triggered.join("; ")]));
+            self.persist_config();
+        }
+    }
+
+    /// 执行正常模式下按键绑定的操作
+    pub async fn perform(&mut self, action: Action) {
+        // 详情页中不修改交易对列表，以免正在查看的交易对被删除或移走
+        if self.detail_view && action.edits_pairs() {
+            let keys = self
+                .keymap
+                .describe(Action::CloseDetail)
+                .unwrap_or_else(|| format!(":{}", Action::CloseDetail.name()));
+            self.set_status(trf("详情页中不能修改交易对列表，按 {} 返回", &[&keys]));
+            return;
+        }
+        match action {
+            Action::Quit => self.quit(),
+            Action::Refresh => {
+                if let Err(e) = self.refresh_data().await {
+                    self.set_status(trf("刷新数据失败: {}", &[&e]));
+                }
+            }
+            Action::SelectUp => self.select_relative(-1),
+            Action::SelectDown => self.select_relative(1),
+            Action::OpenDetail => self.detail_view = self.selected_symbol.is_some(),
+            Action::CloseDetail => self.detail_view = false,
+            Action::MovePairUp => {
+                self.move_selected_pair(-1);
+            }
+            Action::MovePairDown => {
+                self.move_selected_pair(1);
+            }
+            Action::Select1
+            | Action::Select2
+            | Action::Select3
+            | Action::Select4
+            | Action::Select5 => {
+                if let Some(index) = action.select_index() {
+                    self.select_index(index);
+                }
+            }
+            Action::AddPair => self.enter_input_mode(),
+            Action::RemovePair => {
+                if let Some(selected) = self.selected_symbol.clone() {
+                    self.remove_pair(&selected);
+                }
+            }
+            Action::RestoreDefaultPairs => {
+                if let Err(e) = self.restore_default_pairs().await {
+                    self.set_status(trf("刷新数据失败: {}", &[&e]));
+                }
+            }
+            Action::SaveConfig => {
+                if let Err(e) = self.save_config() {
+                    self.set_status(trf("保存配置失败: {}", &[&e]));
+                } else {
+                    self.set_status(tr("配置已保存"));
+                }
+            }
+            Action::CommandMode => self.enter_command_mode(),
+            Action::PrevInterval => self.cycle_interval(-1).await,
+            Action::NextInterval => self.cycle_interval(1).await,
+            // 光标模式下左右键逐根移动光标
+            Action::PanLeft if self.crosshair.is_some() => self.move_crosshair(1),
+            Action::PanRight if self.crosshair.is_some() => self.move_crosshair(-1),
+            Action::PanLeft => self.pan_chart(self.pan_step()),
+            Action::PanRight => self.pan_chart(-self.pan_step()),
+            Action::ToggleCrosshair => self.toggle_crosshair(),
+            Action::ToggleVolume => {
+                self.config.chart.show_volume = !self.config.chart.show_volume;
+                self.persist_config();
+            }
+            Action::ToggleMovingAverages => {
+                self.config.chart.show_moving_averages = !self.config.chart.show_moving_averages;
+                self.persist_config();
+            }
+            Action::ToggleBollinger => {
+                self.config.chart.show_bollinger = !self.config.chart.show_bollinger;
+                self.persist_config();
+            }
+            Action::CycleChartStyle => self.cycle_chart_style(),
+            Action::CycleGridLayout => self.cycle_grid_layout(),
+            Action::NextGridCell => self.move_grid_focus(1),
+            Action::PrevGridCell => self.move_grid_focus(-1),
+            Action::ToggleLogScale => {
+                self.config.chart.log_scale = !self.config.chart.log_scale;
+                let scale = if self.config.chart.log_scale { tr("对数") } else { tr("线性") };
+                self.set_status(trf("价格坐标: {}", &[&scale]));
+                self.persist_config();
+            }
+            Action::ToggleDepthChart => {
+                self.config.depth.show = !self.config.depth.show;
+                self.persist_config();
+            }
+            Action::ShowHelp => {
+                self.input_mode = InputMode::Help;
+                self.help_scroll = 0;
+            }
+            Action::CycleDepthRange => {
+                self.config.depth.range_percent = self.config.depth.next_range();
+                self.set_status(trf("深度图范围: ±{}%", &[&self.config.depth.range_percent]));
+                self.persist_config();
+            }
+            Action::ToggleRsi => {
+                self.config.chart.show_rsi = !self.config.chart.show_rsi;
+                self.persist_config();
+            }
+            Action::ToggleMacd => {
+                self.config.chart.show_macd = !self.config.chart.show_macd;
+                self.persist_config();
+            }
+            Action::ToggleStochastic => {
+                self.config.chart.show_stochastic = !self.config.chart.show_stochastic;
+                self.persist_config();
+            }
+            Action::GrowVolume => self.resize_volume(5),
+            Action::ShrinkVolume => self.resize_volume(-5),
+            Action::ZoomIn => self.zoom_chart(0.8),
+            Action::ZoomOut => self.zoom_chart(1.25),
+            Action::JumpToLatest => self.chart_view.jump_to_latest(),
+        }
+        self.load_requested_history().await;
+    }
+
+    /// 执行 `:` 命令行输入，错误信息显示在状态栏
+    pub async fn execute_command(&mut self, input: &str) {
+        let command = match command::parse(input) {
+            Ok(command) => command,
+            Err(message) => {
+                self.set_status(message);
+                return;
+            }
+        };
+
+        match command {
+            Command::Add(symbols) => {
+                let mut added = false;
+                for symbol in symbols {
+                    added |= self.add_pair(symbol);
+                }
+                if added {
+                    if let Err(e) = self.refresh_data().await {
+                        self.set_status(trf("刷新数据失败: {}", &[&e]));
+                    }
+                }
+            }
+            Command::Remove(symbols) => {
+                for symbol in symbols {
+                    if !self.remove_pair(&symbol) {
+                        self.set_status(trf("交易对不存在: {}", &[&symbol]));
+                    }
+                }
+            }
+            Command::Interval(interval) => self.set_interval(&interval).await,
+            Command::Alert(alert) => {
+                self.set_status(trf("已添加价格提醒: {}", &[&alert]));
+                self.config.alerts.push(alert);
+                self.persist_config();
+            }
+            Command::Compare(symbols) => {
+                if symbols.is_empty() {
+                    self.set_status(tr("已关闭对比"));
+                } else {
+                    self.set_status(trf("对比: {}", &[&symbols.join(" ")]));
+                }
+                self.config.chart.compare_symbols = symbols;
+                self.persist_config();
+            }
+            Command::Theme(name) => {
+                if let Some(preset) = ThemePreset::from_name(&name) {
+                    self.config.theme.set_preset(preset);
+                } else if let Some(price_colors) = PriceColors::from_name(&name) {
+                    self.config.theme.set_price_colors(price_colors);
+                } else {
+                    self.set_status(trf("未知的主题: {}", &[&name]));
+                    return;
+                }
+                // 其余无效的单项颜色已在启动时提示过
+                self.theme = Theme::from_config(&self.config.theme).0;
+                self.set_status(trf("主题: {}", &[&name]));
+                self.persist_config();
+            }
+            Command::Language(language) => {
+                self.config.language = language;
+                i18n::set_language(language);
+                self.set_status(trf("界面语言: {}", &[&language.name()]));
+                self.persist_config();
+            }
+            Command::Action(action) => self.perform(action).await,
+            Command::WriteQuit => {
+                self.perform(Action::SaveConfig).await;
+                self.quit();
+            }
+        }
+    }
+
     pub fn select_symbol(&mut self, symbol: String) {
-        self.selected_symbol = Some(symbol);
+        if self.selected_symbol.as_ref() != Some(&symbol) {
+            // 切换交易对时回到最新K线，恢复默认缩放
+            self.chart_view = ChartView::default();
+            self.crosshair = None;
+        }
+        self.selected_symbol = Some(symbol.clone());
+        // 网格模式下在列表中选择交易对会替换获得焦点的格子
+        if self.config.grid.cells() > 1 {
+            self.assign_grid_cell(self.grid_focus, symbol);
+        }
+    }
+
+    /// 网格各格子实际显示的交易对。配置中不在列表里或重复的交易对
+    /// 由列表中尚未显示的交易对依次补上，列表太短时格子数会少于网格大小。
+    pub fn grid_symbols(&self) -> Vec<String> {
+        let available = self.get_symbols();
+        let mut cells: Vec<String> = Vec::new();
+        for i in 0..self.config.grid.cells() {
+            let configured = self
+                .config
+                .grid
+                .symbols
+                .get(i)
+                .filter(|symbol| available.contains(symbol) && !cells.contains(symbol));
+            let fallback = || available.iter().find(|symbol| !cells.contains(symbol));
+            match configured.or_else(fallback) {
+                Some(symbol) => cells.push(symbol.clone()),
+                None => break,
+            }
+        }
+        cells
+    }
+
+    /// 把交易对放到指定格子，已在其他格子中时两者互换
+    fn assign_grid_cell(&mut self, index: usize, symbol: String) {
+        let mut cells = self.grid_symbols();
+        if index >= cells.len() {
+            return;
+        }
+        match cells.iter().position(|cell| *cell == symbol) {
+            Some(current) => cells.swap(current, index),
+            None => cells[index] = symbol,
+        }
+        if cells != self.config.grid.symbols {
+            self.config.grid.symbols = cells;
+            self.persist_config();
+        }
+    }
+
+    /// 在 1、2、4、6 个图表之间切换
+    fn cycle_grid_layout(&mut self) {
+        let current = self.config.grid.cells();
+        let index = GRID_SIZES.iter().position(|size| *size == current).unwrap_or(0);
+        self.config.grid.size = GRID_SIZES[(index + 1) % GRID_SIZES.len()];
+
+        // 焦点落在当前交易对所在的格子，不在网格中时放到原焦点格子
+        let cells = self.grid_symbols();
+        self.grid_focus = self.grid_focus.min(cells.len().saturating_sub(1));
+        if let Some(symbol) = self.selected_symbol.clone() {
+            match cells.iter().position(|cell| *cell == symbol) {
+                Some(index) => self.grid_focus = index,
+                None => self.assign_grid_cell(self.grid_focus, symbol),
+            }
+        }
+        self.set_status(trf("图表网格: {}", &[&self.config.grid.cells()]));
+        self.persist_config();
+    }
+
+    /// 焦点移动到相邻格子（循环），并选中该格子的交易对
+    pub fn focus_grid_cell(&mut self, index: usize) {
+        let cells = self.grid_symbols();
+        if let Some(symbol) = cells.get(index) {
+            self.grid_focus = index;
+            self.select_symbol(symbol.clone());
+        }
+    }
+
+    fn move_grid_focus(&mut self, delta: isize) {
+        let count = self.grid_symbols().len() as isize;
+        if count > 1 {
+            let next = (self.grid_focus as isize + delta).rem_euclid(count);
+            self.focus_grid_cell(next as usize);
+        }
+    }
+
+    /// 当前选中交易对已加载的K线数量
+    pub fn loaded_candles(&self) -> usize {
+        self.selected_symbol
+            .as_ref()
+            .and_then(|symbol| self.kline_data.get(symbol))
+            .map_or(0, |klines| klines.len())
+    }
+
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let table = self.layout.symbol_table;
+        let chart = self.layout.chart;
+        let over_table = contains(table, mouse.column, mouse.row);
+        let over_chart = contains(chart, mouse.column, mouse.row);
+        let over_grid_cell = self
+            .layout
+            .grid_cells
+            .iter()
+            .position(|cell| contains(*cell, mouse.column, mouse.row));
+        // 跳过上边框和表头两行，以及下边框
+        let over_table_rows =
+            over_table && mouse.row >= table.y + 2 && mouse.row + 1 < table.y + table.height;
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) if over_table_rows => {
+                let row = (mouse.row - table.y - 2) as usize;
+                self.select_index(self.table_state.offset() + row);
+            }
+            // 点击网格中其他格子时切换焦点
+            MouseEventKind::Down(MouseButton::Left) if !over_chart && over_grid_cell.is_some() => {
+                if let Some(index) = over_grid_cell {
+                    self.focus_grid_cell(index);
+                }
+            }
+            MouseEventKind::Down(MouseButton::Left) if over_chart => {
+                self.chart_drag = Some(ChartDrag {
+                    column: mouse.column,
+                    offset: self.chart_view.offset,
+                });
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                if let Some(drag) = self.chart_drag {
+                    let total = self.loaded_candles();
+                    let visible = self.chart_view.window(total).len();
+                    let width = self.layout.chart_plot.width.max(1) as f64;
+                    // 向右拖动查看更早的K线
+                    let columns = mouse.column as f64 - drag.column as f64;
+                    let candles = (columns * visible as f64 / width).round() as isize;
+                    self.chart_view.offset = drag.offset;
+                    if self.chart_view.pan(candles, total) {
+                        self.history_requested = true;
+                    }
+                }
+            }
+            MouseEventKind::Moved if over_chart && self.crosshair.is_some() => {
+                // 光标模式下十字光标跟随鼠标
+                let total = self.loaded_candles();
+                let window = self.chart_view.window(total);
+                if let Some(index) = candle_at_column(self.layout.chart_plot, mouse.column, window.len()) {
+                    self.crosshair = Some(total - 1 - (window.start + index));
+                }
+            }
+            MouseEventKind::Up(MouseButton::Left) => {
+                self.chart_drag = None;
+            }
+            MouseEventKind::ScrollUp if over_table => self.select_relative(-1),
+            MouseEventKind::ScrollDown if over_table => self.select_relative(1),
+            MouseEventKind::ScrollUp if over_chart => self.zoom_chart(0.8),
+            MouseEventKind::ScrollDown if over_chart => self.zoom_chart(1.25),
+            _ => {}
+        }
     }
 
     pub fn should_refresh(&self) -> bool {
@@ -74,37 +1063,201 @@ impl App {
         self.config.get_all_symbols()
     }
 
-    pub fn add_custom_pair(&mut self, symbol: String) -> bool {
-        let success = self.config.add_custom_pair(symbol);
+    pub fn set_status(&mut self, message: impl Into<String>) {
+        self.status_message = Some(message.into());
+    }
+
+    pub fn add_pair(&mut self, symbol: String) -> bool {
+        let symbol_upper = symbol.to_uppercase();
+        let success = self.config.add_pair(symbol);
         if success {
-            // 保存配置到文件
-            if let Err(e) = self.save_config() {
-                eprintln!("保存配置失败: {}", e);
-            }
+            self.set_status(trf("已添加交易对: {}", &[&symbol_upper]));
+            self.persist_config();
+        } else {
+            self.set_status(tr("添加失败: 可能已存在或超过最大数量"));
         }
         success
     }
 
-    pub fn remove_custom_pair(&mut self, symbol: &str) -> bool {
-        let success = self.config.remove_custom_pair(symbol);
+    pub fn remove_pair(&mut self, symbol: &str) -> bool {
+        // 删除前记录位置，以便选中相邻的交易对
+        let symbols = self.get_symbols();
+        let index = symbols.iter().position(|s| s == symbol);
+
+        let success = self.config.remove_pair(symbol);
         if success {
-            // 保存配置到文件
-            if let Err(e) = self.save_config() {
-                eprintln!("保存配置失败: {}", e);
+            self.ticker_prices.remove(symbol);
+            self.kline_data.remove(symbol);
+            self.sparklines.remove(symbol);
+            self.symbol_filters.remove(symbol);
+
+            if self.selected_symbol.as_deref() == Some(symbol) {
+                // 通过命令删除正在查看的交易对时关闭详情页
+                self.detail_view = false;
+                let symbols = self.get_symbols();
+                self.selected_symbol = index
+                    .and_then(|i| symbols.get(i.min(symbols.len().saturating_sub(1))))
+                    .cloned();
             }
+            self.set_status(trf("已删除交易对: {}", &[&symbol]));
+            self.persist_config();
+        }
+        success
+    }
+
+    /// 将选中的交易对上移 (`delta < 0`) 或下移 (`delta > 0`)
+    pub fn move_selected_pair(&mut self, delta: isize) -> bool {
+        let Some(selected) = self.selected_symbol.clone() else {
+            return false;
+        };
+        let success = self.config.move_pair(&selected, delta);
+        if success {
+            self.persist_config();
         }
         success
     }
 
+    pub async fn restore_default_pairs(&mut self) -> Result<()> {
+        self.config.restore_default_pairs();
+        self.set_status(tr("已恢复默认交易对"));
+        self.persist_config();
+
+        let symbols = self.get_symbols();
+        if let Some(selected) = &self.selected_symbol {
+            if !symbols.contains(selected) {
+                self.selected_symbol = None;
+                self.detail_view = false;
+            }
+        }
+        self.ticker_prices.retain(|s, _| symbols.contains(s));
+        self.kline_data.retain(|s, _| symbols.contains(s));
+        self.sparklines.retain(|s, _| symbols.contains(s));
+        self.symbol_filters.retain(|s, _| symbols.contains(s));
+
+        // 默认交易对中可能有尚未加载的数据
+        self.refresh_data().await
+    }
+
+    pub fn select_relative(&mut self, delta: isize) {
+        let symbols = self.get_symbols();
+        if symbols.is_empty() {
+            return;
+        }
+        let next = match self
+            .selected_symbol
+            .as_ref()
+            .and_then(|current| symbols.iter().position(|s| s == current))
+        {
+            Some(index) => (index as isize + delta).clamp(0, symbols.len() as isize - 1) as usize,
+            None => 0,
+        };
+        self.select_symbol(symbols[next].clone());
+    }
+
+    pub fn select_index(&mut self, index: usize) {
+        if let Some(symbol) = self.get_symbols().get(index) {
+            self.select_symbol(symbol.clone());
+        }
+    }
+
+    fn persist_config(&mut self) {
+        // 保存配置到文件
+        if let Err(e) = self.save_config() {
+            self.set_status(trf("保存配置失败: {}", &[&e]));
+        }
+    }
+
     pub fn save_config(&self) -> Result<(), Box<dyn std::error::Error>> {
         self.config.save()
     }
 
+    pub fn enter_command_mode(&mut self) {
+        self.input_mode = InputMode::Command;
+        self.input_buffer.clear();
+        self.history_index = None;
+        self.completion = None;
+    }
+
+    /// 提交命令行：记录历史并执行
+    pub async fn submit_command(&mut self) {
+        let input = self.input_buffer.trim().to_string();
+        self.exit_input_mode();
+        if input.is_empty() {
+            return;
+        }
+
+        if self.command_history.last() != Some(&input) {
+            self.command_history.push(input.clone());
+            if self.command_history.len() > MAX_COMMAND_HISTORY {
+                self.command_history.remove(0);
+            }
+        }
+        self.execute_command(&input).await;
+    }
+
+    /// `delta < 0` 浏览更早的命令，`delta > 0` 浏览更新的命令
+    pub fn browse_history(&mut self, delta: isize) {
+        if self.command_history.is_empty() {
+            return;
+        }
+        let last = self.command_history.len() - 1;
+        let next = match self.history_index {
+            None if delta < 0 => Some(last),
+            None => None,
+            Some(index) => {
+                let index = index as isize + delta;
+                if index > last as isize {
+                    None
+                } else {
+                    Some(index.max(0) as usize)
+                }
+            }
+        };
+        self.history_index = next;
+        self.completion = None;
+        self.input_buffer = next
+            .map(|index| self.command_history[index].clone())
+            .unwrap_or_default();
+    }
+
+    /// Tab 补全命令名、交易对和K线周期，多个候选时循环切换
+    pub fn complete_command(&mut self) {
+        if let Some(completion) = &mut self.completion {
+            completion.index = (completion.index + 1) % completion.candidates.len();
+            self.input_buffer = command::apply_completion(
+                &completion.base,
+                &completion.candidates[completion.index],
+            );
+            return;
+        }
+
+        let candidates = command::completions(&self.input_buffer, &self.get_symbols());
+        match candidates.len() {
+            0 => {}
+            1 => self.input_buffer = command::apply_completion(&self.input_buffer, &candidates[0]),
+            _ => {
+                self.set_status(trf("补全: {}", &[&candidates.join(" ")]));
+                let base = self.input_buffer.clone();
+                self.input_buffer = command::apply_completion(&base, &candidates[0]);
+                self.completion = Some(Completion {
+                    base,
+                    candidates,
+                    index: 0,
+                });
+            }
+        }
+    }
+
     pub fn enter_input_mode(&mut self) {
         self.input_mode = InputMode::AddingPair;
         self.input_buffer.clear();
     }
 
+    /// 滚动帮助窗口，滚动范围的上限在绘制时按内容高度收紧
+    pub fn scroll_help(&mut self, delta: i32) {
+        self.help_scroll = (self.help_scroll as i32 + delta).clamp(0, u16::MAX as i32) as u16;
+    }
+
     pub fn exit_input_mode(&mut self) {
         self.input_mode = InputMode::Normal;
         self.input_buffer.clear();
@@ -112,15 +1265,17 @@ impl App {
 
     pub fn add_input_char(&mut self, c: char) {
         self.input_buffer.push(c);
+        self.completion = None;
     }
 
     pub fn remove_input_char(&mut self) {
         self.input_buffer.pop();
+        self.completion = None;
     }
 
     pub fn submit_input(&mut self) -> bool {
         if !self.input_buffer.is_empty() {
-            let success = self.add_custom_pair(self.input_buffer.clone());
+            let success = self.add_pair(self.input_buffer.clone());
             self.exit_input_mode();
             success
         } else {
@@ -128,4 +1283,50 @@ impl App {
             false
         }
     }
-} 
\ No newline at end of file
+}
+
+fn contains(area: Rect, column: u16, row: u16) -> bool {
+    column >= area.x && column < area.x + area.width && row >= area.y && row < area.y + area.height
+}
+
+/// 图表区域内某一列对应的可见K线下标（与绘制时的映射一致）
+pub fn candle_at_column(plot: Rect, column: u16, visible: usize) -> Option<usize> {
+    if visible == 0 || column < plot.x || column >= plot.x + plot.width {
+        return None;
+    }
+    let x = (column - plot.x) as usize;
+    Some((x * visible / plot.width as usize).min(visible - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zoom_out_stops_at_one_candle_per_column() {
+        // 已加载的K线多于绘图区域的列数
+        let mut view = ChartView {
+            max_visible: Some(80),
+            ..ChartView::default()
+        };
+        assert_eq!(view.window(500), 420..500);
+
+        view.visible = Some(60);
+        assert!(!view.zoom(2.0, 500));
+        assert_eq!(view.window(500).len(), 80);
+        assert!(!view.zoom(2.0, 500));
+        assert_eq!(view.window(500).len(), 80);
+
+        // 平移时窗口大小不变
+        view.pan(30, 500);
+        assert_eq!(view.window(500), 390..470);
+
+        // 列数足够时才需要加载更早的历史
+        let mut wide = ChartView {
+            max_visible: Some(300),
+            ..ChartView::default()
+        };
+        assert!(wide.zoom(2.0, 200));
+        assert_eq!(wide.window(200).len(), 200);
+    }
+}
@@ -0,0 +1,608 @@
+//! 直接写入 `Buffer` 的K线图控件。
+//!
+//! 纵向以 1/8 行为单位计算实体边缘，实体上沿用下部方块字符（`▁`~`▇`），
+//! 下沿用上部方块字符（`▔`、`▀`），影线精确到半行（`╵`、`╷`），
+//! 开盘价等于收盘价的十字星用 `─` 表示，避免在图上消失。
+//! 折线图和面积图使用 Braille 点阵绘制。
+//! 深度图按列累计挂单量，同样以 1/8 行为单位绘制阶梯状的面积。
+
+use crate::api::{KlineData, OrderBook};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    symbols::Marker,
+    widgets::{
+        canvas::{Canvas, Line},
+        Widget,
+    },
+};
+
+/// 已解析的一根K线
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candle {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl From<&KlineData> for Candle {
+    fn from(kline: &KlineData) -> Self {
+        let parse = |value: &String| value.parse::<f64>().unwrap_or(0.0);
+        Self {
+            open: parse(&kline.open),
+            high: parse(&kline.high),
+            low: parse(&kline.low),
+            close: parse(&kline.close),
+        }
+    }
+}
+
+/// 平均K线（Heikin-Ashi）：收盘价为四价均值，开盘价为前一根平均K线开收盘的中点
+pub fn heikin_ashi(candles: &[Candle]) -> Vec<Candle> {
+    let mut result: Vec<Candle> = Vec::with_capacity(candles.len());
+    for candle in candles {
+        let close = (candle.open + candle.high + candle.low + candle.close) / 4.0;
+        let open = match result.last() {
+            Some(previous) => (previous.open + previous.close) / 2.0,
+            None => (candle.open + candle.close) / 2.0,
+        };
+        result.push(Candle {
+            open,
+            high: candle.high.max(open).max(close),
+            low: candle.low.min(open).min(close),
+            close,
+        });
+    }
+    result
+}
+
+/// 第 `i` 根K线所在的列（相对于绘图区域左侧）
+pub fn column_x(i: usize, count: usize, width: u16) -> u16 {
+    (i as f64 / count as f64 * width as f64) as u16
+}
+
+/// 纵轴价格范围及映射方式。对数坐标下按价格的对数等距分布，
+/// 价格范围包含非正数时退回线性坐标。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceScale {
+    pub min: f64,
+    pub max: f64,
+    pub log: bool,
+}
+
+impl PriceScale {
+    pub fn new(min: f64, max: f64, log: bool) -> Self {
+        Self {
+            min,
+            max,
+            log: log && min > 0.0,
+        }
+    }
+
+    /// 映射前的坐标值：对数坐标下为价格的自然对数
+    fn project(&self, price: f64) -> f64 {
+        if self.log {
+            price.max(f64::MIN_POSITIVE).ln()
+        } else {
+            price
+        }
+    }
+
+    /// 价格在 `height` 行中的连续纵坐标：最高价为 0，最低价为 `height`。
+    /// 价格范围为零时所有价格都落在中间。
+    fn offset(&self, price: f64, height: u16) -> f64 {
+        let (top, bottom) = (self.project(self.max), self.project(self.min));
+        let range = top - bottom;
+        if range > 0.0 {
+            (top - self.project(price)) / range * height as f64
+        } else {
+            height as f64 / 2.0
+        }
+    }
+
+    /// 价格所在的行（相对于绘图区域顶部），与K线控件的映射一致
+    pub fn row(&self, price: f64, height: u16) -> u16 {
+        let offset = self.offset(price, height).max(0.0);
+        (offset as u16).min(height.saturating_sub(1))
+    }
+}
+
+pub struct CandleChart<'a> {
+    candles: &'a [Candle],
+    scale: PriceScale,
+    up_style: Style,
+    down_style: Style,
+    highlight: Option<usize>,
+    ohlc_bars: bool,
+}
+
+impl<'a> CandleChart<'a> {
+    /// 默认价格范围为全部K线的最低价到最高价
+    pub fn new(candles: &'a [Candle]) -> Self {
+        let (min, max) = candles.iter().fold((f64::MAX, f64::MIN), |(min, max), candle| {
+            (min.min(candle.low), max.max(candle.high))
+        });
+        Self {
+            candles,
+            scale: PriceScale::new(min, max, false),
+            up_style: Style::default(),
+            down_style: Style::default(),
+            highlight: None,
+            ohlc_bars: false,
+        }
+    }
+
+    /// 指定纵轴价格范围和映射方式，用于与叠加指标、坐标轴保持一致
+    pub fn scale(mut self, scale: PriceScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn up_style(mut self, style: Style) -> Self {
+        self.up_style = style;
+        self
+    }
+
+    pub fn down_style(mut self, style: Style) -> Self {
+        self.down_style = style;
+        self
+    }
+
+    /// 加粗显示十字光标所在的K线
+    pub fn highlight(mut self, index: Option<usize>) -> Self {
+        self.highlight = index;
+        self
+    }
+
+    /// 画成美国线：竖线为最高到最低价，左侧 `┤` 为开盘价，右侧 `├` 为收盘价
+    pub fn ohlc_bars(mut self, ohlc_bars: bool) -> Self {
+        self.ohlc_bars = ohlc_bars;
+        self
+    }
+}
+
+impl Widget for CandleChart<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.candles.is_empty() || area.width == 0 || area.height == 0 {
+            return;
+        }
+        let offset = |price: f64| self.scale.offset(price, area.height);
+
+        for (i, candle) in self.candles.iter().enumerate() {
+            let mut style = if candle.close >= candle.open { self.up_style } else { self.down_style };
+            if self.highlight == Some(i) {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            let x = area.x + column_x(i, self.candles.len(), area.width);
+            let wick = (offset(candle.high), offset(candle.low));
+            let body = (offset(candle.open.max(candle.close)), offset(candle.open.min(candle.close)));
+
+            if self.ohlc_bars {
+                let open_row = self.scale.row(candle.open, area.height);
+                let close_row = self.scale.row(candle.close, area.height);
+                for row in 0..area.height {
+                    let symbol = match (row == open_row, row == close_row) {
+                        (true, true) => Some("┼"),
+                        (true, false) => Some("┤"),
+                        (false, true) => Some("├"),
+                        (false, false) => wick_symbol(wick, row as f64),
+                    };
+                    if let Some(symbol) = symbol {
+                        buf.get_mut(x, area.y + row).set_symbol(symbol).set_style(style);
+                    }
+                }
+                continue;
+            }
+
+            // 不足 1/8 行的实体画成十字星
+            let doji_row = (body.1 - body.0 < 0.125).then(|| (body.0 as u16).min(area.height - 1));
+
+            for row in 0..area.height {
+                let top = row as f64;
+                let symbol = match doji_row {
+                    Some(doji) if doji == row => Some(if wick_crosses(wick, top) { "┼" } else { "─" }),
+                    Some(_) => wick_symbol(wick, top),
+                    None => body_symbol(body, top).or_else(|| wick_symbol(wick, top)),
+                };
+                if let Some(symbol) = symbol {
+                    buf.get_mut(x, area.y + row).set_symbol(symbol).set_style(style);
+                }
+            }
+        }
+    }
+}
+
+/// 收盘价折线，可选填充折线下方的区域（面积图）。值为 `None` 的列留空
+pub struct PriceLine<'a> {
+    values: &'a [Option<f64>],
+    scale: PriceScale,
+    color: Color,
+    fill: Option<Color>,
+}
+
+impl<'a> PriceLine<'a> {
+    pub fn new(values: &'a [Option<f64>], scale: PriceScale) -> Self {
+        Self {
+            values,
+            scale,
+            color: Color::Reset,
+            fill: None,
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn fill(mut self, color: Option<Color>) -> Self {
+        self.fill = color;
+        self
+    }
+}
+
+impl Widget for PriceLine<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.values.is_empty() || area.width == 0 || area.height == 0 {
+            return;
+        }
+        // 在映射后的坐标中绘制；价格范围为零时放宽纵轴，使折线落在中间
+        let scale = self.scale;
+        let (min, max) = (scale.project(scale.min), scale.project(scale.max));
+        let (min, max) = if max > min { (min, max) } else { (min - 1.0, max + 1.0) };
+        // 每根K线的点位于其所在列的中央，与K线控件对齐
+        let count = self.values.len();
+        let points: Vec<Option<(f64, f64)>> = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| value.map(|value| (column_x(i, count, area.width) as f64 + 0.5, scale.project(value))))
+            .collect();
+        let width = area.width as f64;
+
+        Canvas::default()
+            .marker(Marker::Braille)
+            .x_bounds([0.0, width])
+            .y_bounds([min, max])
+            .paint(|ctx| {
+                if let Some(fill) = self.fill {
+                    // 按 Braille 点的横向精度（每列两点）从底部画竖线填充
+                    let filled: Vec<(f64, f64)> = points.iter().flatten().copied().collect();
+                    let mut x = 0.25;
+                    while x < width {
+                        if let Some(value) = interpolate(&filled, x) {
+                            ctx.draw(&Line::new(x, min, x, value, fill));
+                        }
+                        x += 0.5;
+                    }
+                    ctx.layer();
+                }
+                for (i, point) in points.iter().enumerate() {
+                    let Some((x1, y1)) = *point else {
+                        continue;
+                    };
+                    match points.get(i + 1).copied().flatten() {
+                        Some((x2, y2)) => ctx.draw(&Line::new(x1, y1, x2, y2, self.color)),
+                        // 前后都没有数据的点单独画出
+                        None if i == 0 || points[i - 1].is_none() => {
+                            ctx.draw(&Line::new(x1, y1, x1, y1, self.color))
+                        }
+                        None => {}
+                    }
+                }
+            })
+            .render(area, buf);
+    }
+}
+
+/// 价格 `price` 处的累计挂单量：低于中间价时为价格不低于它的买单之和，
+/// 否则为价格不高于它的卖单之和
+pub fn cumulative_depth(book: &OrderBook, price: f64) -> f64 {
+    let Some(mid) = book.mid_price() else {
+        return 0.0;
+    };
+    if price < mid {
+        book.bids.iter().take_while(|(bid, _)| *bid >= price).map(|(_, quantity)| quantity).sum()
+    } else {
+        book.asks.iter().take_while(|(ask, _)| *ask <= price).map(|(_, quantity)| quantity).sum()
+    }
+}
+
+/// 深度图：价格范围内每列的累计挂单量，中间价左侧为买盘，右侧为卖盘
+pub struct DepthChart<'a> {
+    book: &'a OrderBook,
+    min_price: f64,
+    max_price: f64,
+    bid_style: Style,
+    ask_style: Style,
+    mid_style: Style,
+}
+
+impl<'a> DepthChart<'a> {
+    pub fn new(book: &'a OrderBook, min_price: f64, max_price: f64) -> Self {
+        Self {
+            book,
+            min_price,
+            max_price,
+            bid_style: Style::default(),
+            ask_style: Style::default(),
+            mid_style: Style::default(),
+        }
+    }
+
+    pub fn bid_style(mut self, style: Style) -> Self {
+        self.bid_style = style;
+        self
+    }
+
+    pub fn ask_style(mut self, style: Style) -> Self {
+        self.ask_style = style;
+        self
+    }
+
+    /// 中间价竖线的样式
+    pub fn mid_style(mut self, style: Style) -> Self {
+        self.mid_style = style;
+        self
+    }
+
+    /// 纵轴上限：累计挂单量在价格范围两端最大
+    pub fn max_depth(&self) -> f64 {
+        cumulative_depth(self.book, self.min_price).max(cumulative_depth(self.book, self.max_price))
+    }
+
+    /// 价格在 `width` 列中所在的列
+    pub fn column(&self, price: f64, width: u16) -> u16 {
+        let range = self.max_price - self.min_price;
+        if range <= 0.0 {
+            return width / 2;
+        }
+        let x = (price - self.min_price) / range * width as f64;
+        (x.max(0.0) as u16).min(width.saturating_sub(1))
+    }
+}
+
+impl Widget for DepthChart<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        const EIGHTHS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+        let (Some(mid), max_depth) = (self.book.mid_price(), self.max_depth()) else {
+            return;
+        };
+        if area.width == 0 || area.height == 0 || max_depth <= 0.0 {
+            return;
+        }
+        let mid_x = self.column(mid, area.width);
+        let step = (self.max_price - self.min_price) / area.width as f64;
+        for x in 0..area.width {
+            // 每列取列中央的价格
+            let price = self.min_price + (x as f64 + 0.5) * step;
+            let style = if price < mid { self.bid_style } else { self.ask_style };
+            let depth = cumulative_depth(self.book, price);
+            let eighths = (depth / max_depth * area.height as f64 * 8.0).round() as u16;
+            for row in 0..area.height {
+                let filled = eighths.saturating_sub(row * 8).min(8);
+                let y = area.y + area.height - 1 - row;
+                if filled > 0 {
+                    buf.get_mut(area.x + x, y).set_symbol(EIGHTHS[filled as usize - 1]).set_style(style);
+                } else if x == mid_x {
+                    buf.get_mut(area.x + x, y).set_symbol("│").set_style(self.mid_style);
+                }
+            }
+        }
+    }
+}
+
+/// 在相邻两点之间线性插值，超出首尾点的位置取端点值
+fn interpolate(points: &[(f64, f64)], x: f64) -> Option<f64> {
+    let first = points.first()?;
+    let last = points.last()?;
+    if x <= first.0 {
+        return Some(first.1);
+    }
+    if x >= last.0 {
+        return Some(last.1);
+    }
+    points.windows(2).find(|pair| x <= pair[1].0).map(|pair| {
+        let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+        y1 + (y2 - y1) * (x - x1) / (x2 - x1)
+    })
+}
+
+/// 实体在顶部为 `top` 的单元格中的字符，`body` 为实体上下沿的连续纵坐标
+fn body_symbol((body_top, body_bottom): (f64, f64), top: f64) -> Option<&'static str> {
+    const LOWER: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+    let bottom = top + 1.0;
+    if body_bottom <= top || body_top >= bottom {
+        return None;
+    }
+
+    let covers_top = body_top <= top;
+    let covers_bottom = body_bottom >= bottom;
+    let eighths = |fraction: f64| ((fraction * 8.0).round() as usize).clamp(1, 8);
+    Some(match (covers_top, covers_bottom) {
+        (true, true) => "█",
+        // 实体上沿落在本行：从底部向上填充
+        (false, true) => LOWER[eighths(bottom - body_top) - 1],
+        // 实体下沿落在本行：从顶部向下填充
+        (true, false) => match body_bottom - top {
+            f if f >= 0.75 => "█",
+            f if f >= 0.3 => "▀",
+            _ => "▔",
+        },
+        // 实体完全落在本行内部
+        (false, false) => {
+            if bottom - body_bottom < body_top - top {
+                LOWER[eighths(body_bottom - body_top) - 1]
+            } else if body_bottom - body_top >= 0.3 {
+                "▀"
+            } else {
+                "▔"
+            }
+        }
+    })
+}
+
+/// 影线在顶部为 `top` 的单元格中的字符，精确到半行
+fn wick_symbol((wick_top, wick_bottom): (f64, f64), top: f64) -> Option<&'static str> {
+    let middle = top + 0.5;
+    let upper = wick_top < middle && wick_bottom > top;
+    let lower = wick_top < top + 1.0 && wick_bottom > middle;
+    match (upper, lower) {
+        (true, true) => Some("│"),
+        (true, false) => Some("╵"),
+        (false, true) => Some("╷"),
+        (false, false) => None,
+    }
+}
+
+/// 影线是否同时延伸到本行的上方和下方（十字星画成 `┼`）
+fn wick_crosses((wick_top, wick_bottom): (f64, f64), top: f64) -> bool {
+    wick_top < top + 0.5 && wick_bottom > top + 0.5
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle { open, high, low, close }
+    }
+
+    /// 使用默认的无颜色样式渲染，便于与 `Buffer::with_lines` 比较
+    fn render(chart: CandleChart, width: u16, height: u16) -> Buffer {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        chart.render(area, &mut buf);
+        buf
+    }
+
+    fn order_book() -> OrderBook {
+        OrderBook {
+            bids: vec![(9.0, 1.0), (8.0, 1.0)],
+            asks: vec![(11.0, 2.0), (12.0, 2.0)],
+        }
+    }
+
+    #[test]
+    fn accumulates_depth_away_from_mid_price() {
+        let book = order_book();
+        assert_eq!(cumulative_depth(&book, 9.5), 0.0);
+        assert_eq!(cumulative_depth(&book, 8.5), 1.0);
+        assert_eq!(cumulative_depth(&book, 7.0), 2.0);
+        assert_eq!(cumulative_depth(&book, 11.5), 2.0);
+        assert_eq!(cumulative_depth(&book, 13.0), 4.0);
+        assert_eq!(cumulative_depth(&OrderBook::default(), 1.0), 0.0);
+    }
+
+    #[test]
+    fn renders_depth_staircase_with_mid_line() {
+        let book = order_book();
+        let chart = DepthChart::new(&book, 8.0, 12.0);
+        assert_eq!(chart.max_depth(), 4.0);
+        let area = Rect::new(0, 0, 8, 2);
+        let mut buf = Buffer::empty(area);
+        chart.render(area, &mut buf);
+        assert_eq!(buf, Buffer::with_lines(vec!["    │   ", "▄▄  │ ██"]));
+    }
+
+    #[test]
+    fn price_line_leaves_missing_values_blank() {
+        let values = [None, None, Some(1.0), Some(1.0)];
+        let area = Rect::new(0, 0, 4, 1);
+        let mut buf = Buffer::empty(area);
+        PriceLine::new(&values, PriceScale::new(0.0, 2.0, false)).render(area, &mut buf);
+        let drawn: Vec<bool> = (0..4).map(|x| buf.get(x, 0).symbol() != " ").collect();
+        assert_eq!(drawn, [false, false, true, true]);
+    }
+
+    #[test]
+    fn renders_full_cell_body_and_wicks() {
+        let candles = [candle(1.0, 4.0, 0.0, 3.0)];
+        let buf = render(CandleChart::new(&candles), 1, 4);
+        assert_eq!(buf, Buffer::with_lines(vec!["│", "█", "█", "│"]));
+    }
+
+    #[test]
+    fn renders_sub_cell_body_edges() {
+        // 实体上沿在第 1 行中间，下沿在第 2 行的 3/4 处
+        let candles = [candle(2.5, 2.5, 1.25, 1.25)];
+        let buf = render(CandleChart::new(&candles).scale(PriceScale::new(0.0, 4.0, false)), 1, 4);
+        assert_eq!(buf, Buffer::with_lines(vec![" ", "▄", "█", " "]));
+
+        // 实体下沿只占本行上方 1/4
+        let candles = [candle(3.0, 3.0, 1.75, 1.75)];
+        let buf = render(CandleChart::new(&candles).scale(PriceScale::new(0.0, 4.0, false)), 1, 4);
+        assert_eq!(buf, Buffer::with_lines(vec![" ", "█", "▔", " "]));
+    }
+
+    #[test]
+    fn renders_half_cell_wicks() {
+        let candles = [candle(2.0, 3.5, 0.5, 1.0)];
+        let buf = render(CandleChart::new(&candles).scale(PriceScale::new(0.0, 4.0, false)), 1, 4);
+        assert_eq!(buf, Buffer::with_lines(vec!["╷", "│", "█", "╵"]));
+    }
+
+    #[test]
+    fn keeps_flat_candles_visible() {
+        let candles = [candle(2.0, 3.0, 1.0, 2.0), candle(3.0, 3.0, 3.0, 3.0)];
+        let buf = render(CandleChart::new(&candles).scale(PriceScale::new(0.0, 4.0, false)), 2, 4);
+        assert_eq!(buf, Buffer::with_lines(vec!["  ", "│─", "┼ ", "  "]));
+    }
+
+    #[test]
+    fn handles_zero_price_range() {
+        let candles = [candle(5.0, 5.0, 5.0, 5.0); 3];
+        let buf = render(CandleChart::new(&candles), 3, 3);
+        assert_eq!(buf, Buffer::with_lines(vec!["   ", "───", "   "]));
+    }
+
+    #[test]
+    fn spreads_candles_across_columns() {
+        let candles = [candle(0.0, 2.0, 0.0, 2.0), candle(2.0, 2.0, 0.0, 0.0)];
+        let buf = render(CandleChart::new(&candles), 4, 2);
+        assert_eq!(buf, Buffer::with_lines(vec!["█ █ ", "█ █ "]));
+    }
+
+    #[test]
+    fn renders_ohlc_bars() {
+        let candles = [candle(3.0, 4.0, 0.0, 1.0)];
+        let buf = render(CandleChart::new(&candles).ohlc_bars(true), 1, 4);
+        assert_eq!(buf, Buffer::with_lines(vec!["│", "┤", "│", "├"]));
+    }
+
+    #[test]
+    fn computes_heikin_ashi() {
+        let candles = [candle(10.0, 14.0, 8.0, 12.0), candle(12.0, 16.0, 11.0, 15.0)];
+        let ha = heikin_ashi(&candles);
+        assert_eq!(ha[0], candle(11.0, 14.0, 8.0, 11.0));
+        assert_eq!(ha[1], candle(11.0, 16.0, 11.0, 13.5));
+    }
+
+    #[test]
+    fn price_row_matches_candle_mapping() {
+        let scale = PriceScale::new(0.0, 4.0, false);
+        assert_eq!(scale.row(4.0, 4), 0);
+        assert_eq!(scale.row(2.5, 4), 1);
+        assert_eq!(scale.row(0.0, 4), 3);
+        assert_eq!(PriceScale::new(7.0, 7.0, false).row(7.0, 5), 2);
+    }
+
+    #[test]
+    fn log_scale_spaces_prices_by_ratio() {
+        let scale = PriceScale::new(1.0, 100.0, true);
+        assert_eq!(scale.row(100.0, 4), 0);
+        assert_eq!(scale.row(10.0, 4), 2);
+        assert_eq!(scale.row(2.0, 4), 3);
+        // 线性坐标下 10 仍在最底部一行
+        assert_eq!(PriceScale::new(1.0, 100.0, false).row(10.0, 4), 3);
+        // 价格范围包含非正数时退回线性坐标
+        assert!(!PriceScale::new(0.0, 100.0, true).log);
+
+        let candles = [candle(1.0, 100.0, 1.0, 10.0)];
+        let buf = render(CandleChart::new(&candles).scale(scale), 1, 4);
+        assert_eq!(buf, Buffer::with_lines(vec!["│", "│", "█", "█"]));
+    }
+}
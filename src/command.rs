@@ -0,0 +1,276 @@
+use crate::{
+    api::KLINE_INTERVALS,
+    config::{AlertCondition, PriceAlert, PriceColors, ThemePreset},
+    i18n::{tr, trf, Language},
+    keymap::Action,
+};
+
+/// `:` 命令行中可执行的命令
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Add(Vec<String>),
+    Remove(Vec<String>),
+    Interval(String),
+    Alert(PriceAlert),
+    Theme(String),
+    Language(Language),
+    /// 与当前交易对对比涨跌幅的交易对，为空时关闭对比
+    Compare(Vec<String>),
+    /// 与按键绑定相同的操作，如 `:refresh`、`:w`、`:q`
+    Action(Action),
+    /// `:wq` 保存后退出
+    WriteQuit,
+}
+
+/// 内置命令名，用于补全和帮助
+pub const COMMANDS: [&str; 10] = [
+    "add", "rm", "interval", "alert", "theme", "language", "compare", "w", "q", "wq",
+];
+
+pub fn parse(input: &str) -> Result<Command, String> {
+    let mut tokens = input.split_whitespace();
+    let Some(name) = tokens.next() else {
+        return Err(tr("请输入命令").to_string());
+    };
+    let args: Vec<&str> = tokens.collect();
+
+    match name {
+        "add" => symbols_arg(name, &args).map(Command::Add),
+        "rm" | "remove" | "del" => symbols_arg(name, &args).map(Command::Remove),
+        "interval" => match args.as_slice() {
+            [interval] if KLINE_INTERVALS.contains(interval) => {
+                Ok(Command::Interval(interval.to_string()))
+            }
+            [interval] => Err(trf(
+                "无效的K线周期: {} (可用: {})",
+                &[interval, &KLINE_INTERVALS.join(" ")],
+            )),
+            _ => Err(tr("用法: :interval <周期>，如 :interval 1h").to_string()),
+        },
+        "alert" => parse_alert(&args).map(Command::Alert),
+        "theme" => match args.as_slice() {
+            [theme] => Ok(Command::Theme(theme.to_string())),
+            _ => Err(tr("用法: :theme <主题名或涨跌配色>，如 :theme light、:theme red_up").to_string()),
+        },
+        "language" => match args.as_slice() {
+            [language] => Language::from_name(language)
+                .map(Command::Language)
+                .ok_or_else(|| trf("未知的语言: {} (可用: auto zh en)", &[language])),
+            _ => Err(tr("用法: :language auto|zh|en").to_string()),
+        },
+        "compare" if args.is_empty() => Ok(Command::Compare(Vec::new())),
+        "compare" => symbols_arg(name, &args).map(Command::Compare),
+        "w" if args.is_empty() => Ok(Command::Action(Action::SaveConfig)),
+        "q" | "quit" if args.is_empty() => Ok(Command::Action(Action::Quit)),
+        "wq" | "x" if args.is_empty() => Ok(Command::WriteQuit),
+        _ => match Action::from_name(name) {
+            Some(action) if args.is_empty() => Ok(Command::Action(action)),
+            _ => Err(trf("未知命令: {}", &[&name])),
+        },
+    }
+}
+
+fn symbols_arg(name: &str, args: &[&str]) -> Result<Vec<String>, String> {
+    if args.is_empty() {
+        return Err(trf("用法: :{} <交易对>...", &[&name]));
+    }
+    args.iter()
+        .map(|symbol| {
+            if symbol.chars().all(|c| c.is_ascii_alphanumeric()) {
+                Ok(symbol.to_uppercase())
+            } else {
+                Err(trf("无效的交易对: {}", &[symbol]))
+            }
+        })
+        .collect()
+}
+
+/// 解析 `BTCUSDT > 70000`，运算符与价格之间的空格可省略，价格本身不能带空格
+fn parse_alert(args: &[&str]) -> Result<PriceAlert, String> {
+    let usage = || tr("用法: :alert <交易对> >|< <价格>").to_string();
+    let (symbol, rest) = match args {
+        [symbol, operator @ (">" | "<"), price] => (symbol, format!("{}{}", operator, price)),
+        [symbol, condition] => (symbol, condition.to_string()),
+        _ => return Err(usage()),
+    };
+    let (condition, price) = if let Some(price) = rest.strip_prefix('>') {
+        (AlertCondition::Above, price)
+    } else if let Some(price) = rest.strip_prefix('<') {
+        (AlertCondition::Below, price)
+    } else {
+        return Err(usage());
+    };
+    let price = price
+        .parse::<f64>()
+        .ok()
+        .filter(|p| p.is_finite() && *p > 0.0)
+        .ok_or_else(|| trf("无效的价格: {}", &[&price]))?;
+
+    let symbol = symbols_arg("alert", &[symbol])?.remove(0);
+    Ok(PriceAlert { symbol, condition, price })
+}
+
+/// 内置命令的参数格式和用途，用于帮助
+pub fn describe(name: &str) -> (&'static str, &'static str) {
+    match name {
+        "add" => (tr("<交易对>..."), tr("添加交易对")),
+        "rm" => (tr("<交易对>..."), tr("删除交易对")),
+        "interval" => (tr("<周期>"), tr("切换当前交易对的K线周期")),
+        "alert" => (tr("<交易对> >|< <价格>"), tr("添加价格提醒")),
+        "theme" => (tr("<主题名或涨跌配色>"), tr("切换主题或涨跌配色")),
+        "language" => ("auto|zh|en", tr("切换界面语言")),
+        "compare" => (tr("[交易对]..."), tr("对比涨跌幅，不带参数时关闭")),
+        "w" => ("", tr("保存配置")),
+        "q" => ("", tr("退出")),
+        "wq" => ("", tr("保存后退出")),
+        _ => ("", ""),
+    }
+}
+
+/// 返回当前输入最后一个词的补全候选。
+/// 第一个词补全命令名，之后的词补全交易对、K线周期或主题名。
+pub fn completions(input: &str, symbols: &[String]) -> Vec<String> {
+    let ends_with_space = input.ends_with(' ');
+    let tokens: Vec<&str> = input.split_whitespace().collect();
+    let (index, prefix) = match (tokens.last(), ends_with_space) {
+        (Some(last), false) => (tokens.len() - 1, *last),
+        _ => (tokens.len(), ""),
+    };
+
+    let candidates: Vec<String> = if index == 0 {
+        COMMANDS
+            .iter()
+            .map(|c| c.to_string())
+            .chain(Action::ALL.iter().map(|a| a.name().to_string()))
+            .collect()
+    } else {
+        match tokens[0] {
+            "rm" | "remove" | "del" | "compare" => symbols.to_vec(),
+            "alert" if index == 1 => symbols.to_vec(),
+            "interval" if index == 1 => KLINE_INTERVALS.iter().map(|i| i.to_string()).collect(),
+            "theme" if index == 1 => ThemePreset::ALL
+                .iter()
+                .map(|preset| preset.name())
+                .chain(PriceColors::ALL.iter().map(|colors| colors.name()))
+                .map(str::to_string)
+                .collect(),
+            "language" if index == 1 => Language::ALL.iter().map(|l| l.name().to_string()).collect(),
+            _ => Vec::new(),
+        }
+    };
+
+    let upper = prefix.to_uppercase();
+    let mut matches: Vec<String> = candidates
+        .into_iter()
+        .filter(|c| c.starts_with(prefix) || c.starts_with(&upper))
+        .collect();
+    matches.dedup();
+    matches
+}
+
+/// 用补全候选替换输入中的最后一个词
+pub fn apply_completion(input: &str, completion: &str) -> String {
+    let keep = if input.ends_with(' ') {
+        input.len()
+    } else {
+        input.rfind(' ').map_or(0, |i| i + 1)
+    };
+    format!("{}{} ", &input[..keep], completion)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_commands() {
+        let alert = |condition, price| {
+            Command::Alert(PriceAlert { symbol: "BTCUSDT".to_string(), condition, price })
+        };
+        let cases = [
+            ("add btcusdt ETHUSDT", Command::Add(strings(&["BTCUSDT", "ETHUSDT"]))),
+            ("rm solusdt", Command::Remove(strings(&["SOLUSDT"]))),
+            ("del solusdt", Command::Remove(strings(&["SOLUSDT"]))),
+            ("interval 1h", Command::Interval("1h".to_string())),
+            ("alert BTCUSDT > 70000", alert(AlertCondition::Above, 70000.0)),
+            ("alert btcusdt <65000.5", alert(AlertCondition::Below, 65000.5)),
+            ("language en", Command::Language(Language::En)),
+            ("compare", Command::Compare(Vec::new())),
+            ("compare ethusdt", Command::Compare(strings(&["ETHUSDT"]))),
+            ("  w  ", Command::Action(Action::SaveConfig)),
+            ("q", Command::Action(Action::Quit)),
+            ("wq", Command::WriteQuit),
+            ("refresh", Command::Action(Action::Refresh)),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse(input), Ok(expected), "{}", input);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        let inputs = [
+            "",
+            "   ",
+            "add",
+            "add BTC-USDT",
+            "rm",
+            "interval",
+            "interval 7m",
+            "interval 1h 4h",
+            "alert",
+            "alert BTCUSDT",
+            "alert BTCUSDT = 70000",
+            "alert BTCUSDT > abc",
+            // 价格中间的空格多半是输入错误，不能拼接成 65000
+            "alert BTCUSDT < 6 5000",
+            "alert BTCUSDT <6 5000",
+            "alert BTCUSDT 70000",
+            "alert BTCUSDT > -1",
+            "alert BTCUSDT > inf",
+            "language fr",
+            "theme",
+            "w now",
+            "refresh now",
+            "frobnicate",
+        ];
+        for input in inputs {
+            assert!(parse(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn completes_commands_symbols_and_intervals() {
+        let symbols = strings(&["BTCUSDT", "BNBUSDT", "ETHUSDT"]);
+        let cases: [(&str, &[&str]); 7] = [
+            ("int", &["interval"]),
+            ("w", &["w", "wq"]),
+            ("rm b", &["BTCUSDT", "BNBUSDT"]),
+            ("compare BTCUSDT ", &["BTCUSDT", "BNBUSDT", "ETHUSDT"]),
+            ("interval 1", &["1s", "1m", "15m", "1h", "12h", "1d", "1w", "1M"]),
+            ("language e", &["en"]),
+            ("add b", &[]),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(completions(input, &symbols), strings(expected), "{}", input);
+        }
+    }
+
+    #[test]
+    fn completion_cycles_from_the_original_input() {
+        let symbols = strings(&["BTCUSDT", "BNBUSDT"]);
+        let base = "rm b";
+        let candidates = completions(base, &symbols);
+        // 连续按 Tab 时每次都在原始输入上替换最后一个词
+        let cycled: Vec<String> = (0..3)
+            .map(|i| apply_completion(base, &candidates[i % candidates.len()]))
+            .collect();
+        assert_eq!(cycled, strings(&["rm BTCUSDT ", "rm BNBUSDT ", "rm BTCUSDT "]));
+        assert_eq!(apply_completion("rm BTCUSDT ", "BNBUSDT"), "rm BTCUSDT BNBUSDT ");
+        assert_eq!(apply_completion("", "add"), "add ");
+    }
+}
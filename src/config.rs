@@ -1,29 +1,548 @@
+use crate::api::KLINE_INTERVALS;
+use crate::i18n::{tr, Language};
 use config::{Config, ConfigError, Environment, File};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AppConfig {
     pub refresh_interval: u64,
+    #[serde(default)]
     pub symbols: Vec<String>,
     pub binance_api_url: String,
+    /// 未单独设置周期的交易对使用的K线周期
+    #[serde(default = "default_interval")]
+    pub default_interval: String,
+    #[serde(default)]
     pub theme: ThemeConfig,
     pub trading_pairs: TradingPairsConfig,
+    /// 操作名到按键列表的映射，未配置的操作使用默认按键
+    #[serde(default)]
+    pub keybindings: BTreeMap<String, Vec<String>>,
+    /// 尚未触发的价格提醒，触发后自动移除
+    #[serde(default)]
+    pub alerts: Vec<PriceAlert>,
+    /// 每个交易对上次选择的K线周期
+    #[serde(default)]
+    pub symbol_intervals: BTreeMap<String, String>,
+    /// 每个交易对上次选择的图表样式
+    #[serde(default)]
+    pub symbol_chart_styles: BTreeMap<String, ChartStyle>,
+    #[serde(default)]
+    pub chart: ChartConfig,
+    #[serde(default)]
+    pub grid: GridConfig,
+    #[serde(default)]
+    pub depth: DepthConfig,
+    #[serde(default)]
+    pub layout: LayoutConfig,
+    /// 最新价变化时价格单元格闪烁的时长（毫秒），0 为关闭
+    #[serde(default = "default_price_flash_ms")]
+    pub price_flash_ms: u64,
+    /// 界面语言：auto、zh 或 en
+    #[serde(default)]
+    pub language: Language,
+    /// 加载时迁移了旧版配置，需要写回文件
+    #[serde(skip)]
+    pub needs_save: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChartConfig {
+    /// 价格轴是否使用对数坐标
+    pub log_scale: bool,
+    /// 是否在K线图下方显示成交量
+    pub show_volume: bool,
+    /// 成交量区域占图表高度的百分比
+    pub volume_height_percent: u16,
+    /// 成交量均线周期，0 表示不显示
+    pub volume_ma_period: usize,
+    /// 是否显示均线叠加
+    pub show_moving_averages: bool,
+    pub moving_averages: Vec<MovingAverageConfig>,
+    /// 是否显示布林带
+    pub show_bollinger: bool,
+    pub bollinger: BollingerConfig,
+    /// 每个震荡指标副图的高度（行数，含标题行）
+    pub oscillator_height: u16,
+    pub show_rsi: bool,
+    pub rsi: RsiConfig,
+    pub show_macd: bool,
+    pub macd: MacdConfig,
+    pub show_stochastic: bool,
+    pub stochastic: StochasticConfig,
+    /// 与当前交易对对比涨跌幅的交易对，为空时显示普通K线图
+    pub compare_symbols: Vec<String>,
+}
+
+impl Default for ChartConfig {
+    fn default() -> Self {
+        Self {
+            log_scale: false,
+            show_volume: true,
+            volume_height_percent: 20,
+            volume_ma_period: 20,
+            show_moving_averages: true,
+            moving_averages: vec![
+                MovingAverageConfig {
+                    kind: MovingAverageKind::Sma,
+                    period: 7,
+                    color: "#ffff00".to_string(),
+                },
+                MovingAverageConfig {
+                    kind: MovingAverageKind::Sma,
+                    period: 25,
+                    color: "#ff00ff".to_string(),
+                },
+                MovingAverageConfig {
+                    kind: MovingAverageKind::Ema,
+                    period: 99,
+                    color: "#00bfff".to_string(),
+                },
+            ],
+            show_bollinger: false,
+            bollinger: BollingerConfig::default(),
+            oscillator_height: 7,
+            show_rsi: false,
+            rsi: RsiConfig::default(),
+            show_macd: false,
+            macd: MacdConfig::default(),
+            show_stochastic: false,
+            stochastic: StochasticConfig::default(),
+            compare_symbols: Vec::new(),
+        }
+    }
+}
+
+/// 可选的网格图表数量，1 表示只显示一个图表
+pub const GRID_SIZES: [usize; 4] = [1, 2, 4, 6];
+
+/// 多图网格布局
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GridConfig {
+    /// 同时显示的图表数量，可选 1、2、4、6
+    pub size: usize,
+    /// 各格子中的交易对，按从左到右、从上到下的顺序
+    pub symbols: Vec<String>,
+}
+
+impl Default for GridConfig {
+    fn default() -> Self {
+        Self {
+            size: 1,
+            symbols: Vec::new(),
+        }
+    }
+}
+
+impl GridConfig {
+    /// 格子数量，配置无效时按单图处理
+    pub fn cells(&self) -> usize {
+        if GRID_SIZES.contains(&self.size) {
+            self.size
+        } else {
+            1
+        }
+    }
+}
+
+/// 深度图可选的价格范围（中间价上下的百分比）
+pub const DEPTH_RANGES: [f64; 4] = [0.5, 1.0, 2.0, 5.0];
+
+/// 深度图：在K线图位置显示选中交易对的累计买卖盘
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DepthConfig {
+    /// 是否以深度图代替K线图
+    pub show: bool,
+    /// 显示中间价上下多少百分比范围内的挂单
+    pub range_percent: f64,
+    /// 获取的挂单档位数量（币安接口限制为 5000）
+    pub limit: u32,
+}
+
+impl Default for DepthConfig {
+    fn default() -> Self {
+        Self {
+            show: false,
+            range_percent: 1.0,
+            limit: 1000,
+        }
+    }
+}
+
+impl DepthConfig {
+    /// 切换到下一个更大的价格范围，超过最大值后回到最小值
+    pub fn next_range(&self) -> f64 {
+        DEPTH_RANGES
+            .iter()
+            .copied()
+            .find(|range| *range > self.range_percent)
+            .unwrap_or(DEPTH_RANGES[0])
+    }
+}
+
+/// 界面布局：交易对列表与图表的划分比例，以及窄终端下改为上下排列的宽度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutConfig {
+    /// 左右排列时交易对列表所占宽度的百分比
+    pub table_percent: u16,
+    /// 交易对列表的最大宽度，宽终端上多出的空间留给图表
+    pub table_max_width: u16,
+    /// 终端宽度小于该值时，交易对列表和图表改为上下排列
+    pub stack_below: u16,
+    /// 上下排列时交易对列表所占高度的百分比
+    pub stacked_table_percent: u16,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            table_percent: 40,
+            table_max_width: 64,
+            stack_below: 100,
+            stacked_table_percent: 35,
+        }
+    }
+}
+
+/// 主图的绘制样式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChartStyle {
+    #[default]
+    Candles,
+    OhlcBars,
+    Line,
+    Area,
+    HeikinAshi,
+}
+
+impl ChartStyle {
+    pub const ALL: [ChartStyle; 5] = [
+        ChartStyle::Candles,
+        ChartStyle::OhlcBars,
+        ChartStyle::Line,
+        ChartStyle::Area,
+        ChartStyle::HeikinAshi,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ChartStyle::Candles => tr("蜡烛图"),
+            ChartStyle::OhlcBars => tr("美国线"),
+            ChartStyle::Line => tr("折线图"),
+            ChartStyle::Area => tr("面积图"),
+            ChartStyle::HeikinAshi => tr("平均K线"),
+        }
+    }
+
+    /// 按 `ALL` 的顺序循环切换
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|style| *style == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MovingAverageKind {
+    Sma,
+    Ema,
+    Wma,
+}
+
+impl MovingAverageKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            MovingAverageKind::Sma => "MA",
+            MovingAverageKind::Ema => "EMA",
+            MovingAverageKind::Wma => "WMA",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MovingAverageConfig {
+    pub kind: MovingAverageKind,
+    pub period: usize,
+    /// 颜色，如 `#ffff00` 或 `yellow`
+    pub color: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BollingerConfig {
+    pub period: usize,
+    /// 上下轨距中轨的标准差倍数
+    pub std_dev: f64,
+    pub color: String,
+}
+
+impl Default for BollingerConfig {
+    fn default() -> Self {
+        Self {
+            period: 20,
+            std_dev: 2.0,
+            color: "#808080".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RsiConfig {
+    pub period: usize,
+    /// 超买线
+    pub overbought: f64,
+    /// 超卖线
+    pub oversold: f64,
+}
+
+impl Default for RsiConfig {
+    fn default() -> Self {
+        Self {
+            period: 14,
+            overbought: 70.0,
+            oversold: 30.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MacdConfig {
+    pub fast: usize,
+    pub slow: usize,
+    pub signal: usize,
+}
+
+impl Default for MacdConfig {
+    fn default() -> Self {
+        Self {
+            fast: 12,
+            slow: 26,
+            signal: 9,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StochasticConfig {
+    pub k_period: usize,
+    pub k_smooth: usize,
+    pub d_period: usize,
+    pub overbought: f64,
+    pub oversold: f64,
+}
+
+impl Default for StochasticConfig {
+    fn default() -> Self {
+        Self {
+            k_period: 14,
+            k_smooth: 3,
+            d_period: 3,
+            overbought: 80.0,
+            oversold: 20.0,
+        }
+    }
+}
+
+fn default_interval() -> String {
+    "5m".to_string()
+}
+
+fn default_price_flash_ms() -> u64 {
+    1000
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertCondition {
+    Above,
+    Below,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceAlert {
+    pub symbol: String,
+    pub condition: AlertCondition,
+    pub price: f64,
+}
+
+impl PriceAlert {
+    pub fn is_triggered(&self, price: f64) -> bool {
+        match self.condition {
+            AlertCondition::Above => price >= self.price,
+            AlertCondition::Below => price <= self.price,
+        }
+    }
+}
+
+impl std::fmt::Display for PriceAlert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let op = match self.condition {
+            AlertCondition::Above => ">",
+            AlertCondition::Below => "<",
+        };
+        write!(f, "{} {} {}", self.symbol, op, self.price)
+    }
+}
+
+/// 界面配色：预设主题，加上可选的单项颜色覆盖
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ThemeConfig {
-    pub primary: String,
-    pub secondary: String,
-    pub background: String,
-    pub text: String,
+    pub preset: ThemePreset,
+    /// 涨跌使用的颜色
+    pub price_colors: PriceColors,
+    /// 以下颜色覆盖预设中的对应颜色，支持 `#rrggbb` 或颜色名
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secondary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub up: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub down: Option<String>,
+}
+
+impl ThemeConfig {
+    /// 切换预设主题，同时清除覆盖预设的界面颜色（涨跌颜色保留）
+    pub fn set_preset(&mut self, preset: ThemePreset) {
+        self.preset = preset;
+        self.primary = None;
+        self.secondary = None;
+        self.background = None;
+        self.text = None;
+    }
+
+    /// 旧版配置总会写入固定的界面颜色，与旧默认值相同的颜色视为未设置，
+    /// 以免覆盖预设主题
+    fn migrate_legacy_colors(&mut self) {
+        for (color, legacy) in [
+            (&mut self.primary, "#00ff00"),
+            (&mut self.secondary, "#ffff00"),
+            (&mut self.background, "#000000"),
+            (&mut self.text, "#ffffff"),
+        ] {
+            if color.as_deref().is_some_and(|color| color.eq_ignore_ascii_case(legacy)) {
+                *color = None;
+            }
+        }
+    }
+
+    /// 切换涨跌配色，同时清除单独设置的涨跌颜色
+    pub fn set_price_colors(&mut self, price_colors: PriceColors) {
+        self.price_colors = price_colors;
+        self.up = None;
+        self.down = None;
+    }
+}
+
+/// 内置的主题预设
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemePreset {
+    #[default]
+    Dark,
+    Light,
+    Solarized,
+    HighContrast,
+}
+
+impl ThemePreset {
+    pub const ALL: [ThemePreset; 4] = [
+        ThemePreset::Dark,
+        ThemePreset::Light,
+        ThemePreset::Solarized,
+        ThemePreset::HighContrast,
+    ];
+
+    /// 配置文件和 `:theme` 命令中使用的名称
+    pub fn name(self) -> &'static str {
+        match self {
+            ThemePreset::Dark => "dark",
+            ThemePreset::Light => "light",
+            ThemePreset::Solarized => "solarized",
+            ThemePreset::HighContrast => "high_contrast",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|preset| preset.name() == name)
+    }
+}
+
+/// 涨跌配色
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceColors {
+    /// 绿涨红跌
+    #[default]
+    GreenUp,
+    /// 红涨绿跌（中国大陆、日本等市场的习惯）
+    RedUp,
+    /// 蓝涨橙跌，便于红绿色觉障碍者区分
+    BlueOrange,
+}
+
+impl PriceColors {
+    pub const ALL: [PriceColors; 3] = [PriceColors::GreenUp, PriceColors::RedUp, PriceColors::BlueOrange];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PriceColors::GreenUp => "green_up",
+            PriceColors::RedUp => "red_up",
+            PriceColors::BlueOrange => "blue_orange",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|colors| colors.name() == name)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TradingPairsConfig {
-    pub default_pairs: Vec<String>,
-    pub custom_pairs: Vec<String>,
+    /// 用户可完全控制顺序的交易对列表
+    #[serde(default)]
+    pub pairs: Vec<String>,
     pub max_display_pairs: usize,
+    // 旧版配置字段，仅用于迁移，不再写回文件
+    #[serde(default, skip_serializing)]
+    default_pairs: Option<Vec<String>>,
+    #[serde(default, skip_serializing)]
+    custom_pairs: Option<Vec<String>>,
+}
+
+pub const DEFAULT_PAIRS: [&str; 8] = [
+    "BTCUSDT",
+    "ETHUSDT",
+    "BNBUSDT",
+    "ADAUSDT",
+    "DOTUSDT",
+    "LINKUSDT",
+    "LTCUSDT",
+    "XRPUSDT",
+];
+
+/// 交易对名作为键时统一为大写
+fn uppercase_keys<V>(map: BTreeMap<String, V>) -> BTreeMap<String, V> {
+    map.into_iter().map(|(key, value)| (key.to_uppercase(), value)).collect()
+}
+
+pub fn default_pairs() -> Vec<String> {
+    DEFAULT_PAIRS.iter().map(|s| s.to_string()).collect()
 }
 
 impl Default for AppConfig {
@@ -36,26 +555,25 @@ impl Default for AppConfig {
                 "BNBUSDT".to_string(),
             ],
             binance_api_url: "https://api.binance.com".to_string(),
-            theme: ThemeConfig {
-                primary: "#00ff00".to_string(),
-                secondary: "#ffff00".to_string(),
-                background: "#000000".to_string(),
-                text: "#ffffff".to_string(),
-            },
+            default_interval: default_interval(),
+            theme: ThemeConfig::default(),
             trading_pairs: TradingPairsConfig {
-                default_pairs: vec![
-                    "BTCUSDT".to_string(),
-                    "ETHUSDT".to_string(),
-                    "BNBUSDT".to_string(),
-                    "ADAUSDT".to_string(),
-                    "DOTUSDT".to_string(),
-                    "LINKUSDT".to_string(),
-                    "LTCUSDT".to_string(),
-                    "XRPUSDT".to_string(),
-                ],
-                custom_pairs: vec![],
+                pairs: default_pairs(),
                 max_display_pairs: 20,
+                default_pairs: None,
+                custom_pairs: None,
             },
+            keybindings: BTreeMap::new(),
+            alerts: Vec::new(),
+            symbol_intervals: BTreeMap::new(),
+            symbol_chart_styles: BTreeMap::new(),
+            chart: ChartConfig::default(),
+            grid: GridConfig::default(),
+            depth: DepthConfig::default(),
+            layout: LayoutConfig::default(),
+            price_flash_ms: default_price_flash_ms(),
+            language: Language::Auto,
+            needs_save: false,
         }
     }
 }
@@ -71,10 +589,50 @@ impl AppConfig {
             .add_source(Environment::with_prefix("BINANCE_PORTAL"))
             .build()?;
 
-        config.try_deserialize()
+        Self::from_config(config)
+    }
+
+    /// 反序列化配置并迁移旧版字段
+    fn from_config(config: Config) -> Result<Self, ConfigError> {
+        let mut app_config: Self = config.try_deserialize()?;
+        // config 库读取时会把表的键转为小写，按交易对保存的设置恢复为大写
+        app_config.symbol_intervals = uppercase_keys(std::mem::take(&mut app_config.symbol_intervals));
+        app_config.symbol_chart_styles = uppercase_keys(std::mem::take(&mut app_config.symbol_chart_styles));
+        // 迁移后由应用启动时写回，旧字段不会再出现在配置文件中
+        app_config.needs_save = app_config.migrate_legacy_pairs();
+        // 只有旧版格式的配置才清除旧默认颜色，新格式中的同色设置是用户有意选择的
+        if app_config.needs_save {
+            app_config.theme.migrate_legacy_colors();
+        }
+        Ok(app_config)
+    }
+
+    /// 将旧版 `default_pairs` / `custom_pairs` 合并为统一的 `pairs` 列表。
+    /// 返回是否发生了迁移。
+    fn migrate_legacy_pairs(&mut self) -> bool {
+        let legacy_default = self.trading_pairs.default_pairs.take();
+        let legacy_custom = self.trading_pairs.custom_pairs.take();
+        if legacy_default.is_none() && legacy_custom.is_none() {
+            return false;
+        }
+
+        // 已有新格式列表时以新列表为准，仅丢弃旧字段
+        if self.trading_pairs.pairs.is_empty() {
+            let legacy = legacy_default
+                .unwrap_or_default()
+                .into_iter()
+                .chain(legacy_custom.unwrap_or_default());
+            for symbol in legacy {
+                let symbol_upper = symbol.to_uppercase();
+                if !self.trading_pairs.pairs.contains(&symbol_upper) {
+                    self.trading_pairs.pairs.push(symbol_upper);
+                }
+            }
+        }
+
+        true
     }
 
-    #[allow(dead_code)]
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
         let config_dir = dirs::config_dir()
             .unwrap_or_else(|| PathBuf::from("."))
@@ -88,45 +646,222 @@ impl AppConfig {
         Ok(())
     }
 
-    pub fn get_all_symbols(&self) -> Vec<String> {
-        let mut all_symbols = self.trading_pairs.default_pairs.clone();
-        all_symbols.extend(self.trading_pairs.custom_pairs.clone());
-        
-        // 去重并限制数量
-        let mut unique_symbols = Vec::new();
-        for symbol in all_symbols {
-            if !unique_symbols.contains(&symbol) && unique_symbols.len() < self.trading_pairs.max_display_pairs {
-                unique_symbols.push(symbol);
-            }
+    /// 交易对的K线周期，配置中无效的周期回退到默认值
+    pub fn interval_for(&self, symbol: &str) -> &str {
+        match self.symbol_intervals.get(symbol) {
+            Some(interval) if KLINE_INTERVALS.contains(&interval.as_str()) => interval,
+            _ => self.global_interval(),
         }
-        
-        unique_symbols
     }
 
-    pub fn add_custom_pair(&mut self, symbol: String) -> bool {
+    /// 默认K线周期，配置无效时回退到 5m
+    pub fn global_interval(&self) -> &str {
+        if KLINE_INTERVALS.contains(&self.default_interval.as_str()) {
+            &self.default_interval
+        } else {
+            "5m"
+        }
+    }
+
+    pub fn set_interval(&mut self, symbol: &str, interval: &str) {
+        self.symbol_intervals.insert(symbol.to_string(), interval.to_string());
+    }
+
+    /// 交易对的图表样式，未设置时为蜡烛图
+    pub fn chart_style_for(&self, symbol: &str) -> ChartStyle {
+        self.symbol_chart_styles.get(symbol).copied().unwrap_or_default()
+    }
+
+    pub fn set_chart_style(&mut self, symbol: &str, style: ChartStyle) {
+        self.symbol_chart_styles.insert(symbol.to_string(), style);
+    }
+
+    pub fn get_all_symbols(&self) -> Vec<String> {
+        self.trading_pairs
+            .pairs
+            .iter()
+            .take(self.trading_pairs.max_display_pairs)
+            .cloned()
+            .collect()
+    }
+
+    pub fn add_pair(&mut self, symbol: String) -> bool {
         let symbol_upper = symbol.to_uppercase();
         
         // 检查是否已经存在
-        if self.trading_pairs.custom_pairs.contains(&symbol_upper) {
+        if self.trading_pairs.pairs.contains(&symbol_upper) {
             return false;
         }
         
         // 检查是否超过最大数量
-        if self.get_all_symbols().len() >= self.trading_pairs.max_display_pairs {
+        if self.trading_pairs.pairs.len() >= self.trading_pairs.max_display_pairs {
             return false;
         }
         
-        self.trading_pairs.custom_pairs.push(symbol_upper);
+        self.trading_pairs.pairs.push(symbol_upper);
         true
     }
 
-    pub fn remove_custom_pair(&mut self, symbol: &str) -> bool {
+    pub fn remove_pair(&mut self, symbol: &str) -> bool {
         let symbol_upper = symbol.to_uppercase();
-        if let Some(index) = self.trading_pairs.custom_pairs.iter().position(|s| s == &symbol_upper) {
-            self.trading_pairs.custom_pairs.remove(index);
+        if let Some(index) = self.trading_pairs.pairs.iter().position(|s| s == &symbol_upper) {
+            self.trading_pairs.pairs.remove(index);
             true
         } else {
             false
         }
     }
-} 
\ No newline at end of file
+
+    /// 将交易对在列表中移动 `delta` 个位置，越界时不移动。
+    pub fn move_pair(&mut self, symbol: &str, delta: isize) -> bool {
+        let pairs = &mut self.trading_pairs.pairs;
+        let Some(index) = pairs.iter().position(|s| s == symbol) else {
+            return false;
+        };
+        let target = index as isize + delta;
+        if target < 0 || target as usize >= pairs.len() {
+            return false;
+        }
+        let symbol = pairs.remove(index);
+        pairs.insert(target as usize, symbol);
+        true
+    }
+
+    pub fn restore_default_pairs(&mut self) {
+        self.trading_pairs.pairs = default_pairs();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use config::FileFormat;
+
+    fn parse(toml: &str) -> AppConfig {
+        let config = Config::builder()
+            .add_source(File::from_str(toml, FileFormat::Toml))
+            .build()
+            .unwrap();
+        AppConfig::from_config(config).unwrap()
+    }
+
+    const BASE: &str = r#"
+refresh_interval = 20
+binance_api_url = "https://api.binance.com"
+"#;
+
+    /// 保存后重新加载
+    fn round_trip(config: &AppConfig) -> AppConfig {
+        parse(&toml::to_string_pretty(config).unwrap())
+    }
+
+    #[test]
+    fn symbol_intervals_survive_save_and_load() {
+        let mut config = AppConfig::default();
+        config.set_interval("BTCUSDT", "1h");
+        config.set_interval("ETHUSDT", "1M");
+
+        let loaded = round_trip(&config);
+        assert_eq!(loaded.interval_for("BTCUSDT"), "1h");
+        assert_eq!(loaded.interval_for("ETHUSDT"), "1M");
+        assert_eq!(loaded.interval_for("BNBUSDT"), config.global_interval());
+        // 再次保存不会产生重复的键
+        assert_eq!(round_trip(&loaded).symbol_intervals, config.symbol_intervals);
+    }
+
+    #[test]
+    fn symbol_chart_styles_survive_save_and_load() {
+        let mut config = AppConfig::default();
+        config.set_chart_style("BTCUSDT", ChartStyle::HeikinAshi);
+        config.set_chart_style("ETHUSDT", ChartStyle::Line);
+
+        let loaded = round_trip(&config);
+        assert_eq!(loaded.chart_style_for("BTCUSDT"), ChartStyle::HeikinAshi);
+        assert_eq!(loaded.chart_style_for("ETHUSDT"), ChartStyle::Line);
+        assert_eq!(loaded.chart_style_for("BNBUSDT"), ChartStyle::default());
+        assert_eq!(round_trip(&loaded).symbol_chart_styles, config.symbol_chart_styles);
+    }
+
+    #[test]
+    fn drops_legacy_default_theme_colors() {
+        let config = parse(&format!(
+            r##"{}
+[trading_pairs]
+max_display_pairs = 20
+default_pairs = ["BTCUSDT"]
+custom_pairs = []
+
+[theme]
+preset = "light"
+primary = "#00FF00"
+secondary = "#ffff00"
+background = "#000000"
+text = "#123456"
+"##,
+            BASE
+        ));
+        assert_eq!(config.theme.preset, ThemePreset::Light);
+        assert_eq!(config.theme.primary, None);
+        assert_eq!(config.theme.secondary, None);
+        assert_eq!(config.theme.background, None);
+        // 用户改过的颜色保留
+        assert_eq!(config.theme.text.as_deref(), Some("#123456"));
+        assert!(config.needs_save);
+    }
+
+    #[test]
+    fn keeps_default_colored_theme_in_new_format() {
+        let config = parse(&format!(
+            r##"{}
+[trading_pairs]
+max_display_pairs = 20
+pairs = ["BTCUSDT"]
+
+[theme]
+preset = "light"
+primary = "#00ff00"
+background = "#000000"
+"##,
+            BASE
+        ));
+        assert_eq!(config.theme.primary.as_deref(), Some("#00ff00"));
+        assert_eq!(config.theme.background.as_deref(), Some("#000000"));
+        assert!(!config.needs_save);
+    }
+
+    #[test]
+    fn migrates_legacy_pairs_in_order_without_duplicates() {
+        let config = parse(&format!(
+            r#"{}
+[trading_pairs]
+max_display_pairs = 20
+default_pairs = ["BTCUSDT", "ETHUSDT"]
+custom_pairs = ["solusdt", "ETHUSDT", "BTCUSDT", "DOGEUSDT"]
+"#,
+            BASE
+        ));
+        assert_eq!(config.trading_pairs.pairs, ["BTCUSDT", "ETHUSDT", "SOLUSDT", "DOGEUSDT"]);
+        assert!(config.needs_save);
+        // 旧字段不再写回
+        let saved = toml::to_string_pretty(&config).unwrap();
+        assert!(!saved.contains("default_pairs") && !saved.contains("custom_pairs"));
+    }
+
+    #[test]
+    fn new_pairs_list_takes_precedence_over_legacy_fields() {
+        let config = parse(&format!(
+            r#"{}
+[trading_pairs]
+max_display_pairs = 20
+pairs = ["ETHUSDT"]
+default_pairs = ["BTCUSDT"]
+"#,
+            BASE
+        ));
+        assert_eq!(config.trading_pairs.pairs, ["ETHUSDT"]);
+        assert!(config.needs_save);
+
+        let config = parse(&format!("{}\n[trading_pairs]\nmax_display_pairs = 20\npairs = [\"ETHUSDT\"]\n", BASE));
+        assert!(!config.needs_save);
+    }
+}
@@ -1,5 +1,5 @@
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEvent, KeyEventKind, MouseEvent},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -17,7 +17,8 @@ pub enum InputMode {
 
 #[derive(Debug)]
 pub enum EventType {
-    Input(KeyCode),
+    Input(KeyEvent),
+    Mouse(MouseEvent),
     Tick,
     #[allow(dead_code)]
     Refresh,
@@ -46,8 +47,15 @@ impl EventHandler {
             .unwrap_or_else(|| Duration::from_secs(0));
 
         if crossterm::event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                return Ok(Some(EventType::Input(key.code)));
+            match event::read()? {
+                // Windows 下会同时上报按下和释放，只处理按下
+                Event::Key(key) if key.kind != KeyEventKind::Release => {
+                    return Ok(Some(EventType::Input(key)));
+                }
+                Event::Mouse(mouse) => {
+                    return Ok(Some(EventType::Mouse(mouse)));
+                }
+                _ => {}
             }
         }
 
@@ -0,0 +1,109 @@
+//! 价格、数量和涨跌幅的显示格式。
+//!
+//! 价格和数量按交易所 `exchangeInfo` 中的 `tickSize` / `stepSize` 取精度，
+//! 去掉末尾多余的 0，整数部分加千位分隔符。尚未获取到精度时按数值本身的位数显示。
+
+use crate::api::SymbolFilters;
+
+/// 步长对应的小数位数，如 `0.01` -> 2，`1` -> 0
+pub fn decimals(step: f64) -> usize {
+    if step <= 0.0 || !step.is_finite() {
+        return 0;
+    }
+    step.to_string().split_once('.').map_or(0, |(_, fraction)| fraction.len())
+}
+
+/// 保留 `decimals` 位小数后去掉末尾的 0，并给整数部分加千位分隔符。
+/// `decimals` 为 `None` 时使用数值的最短表示。
+pub fn format_number(value: f64, decimals: Option<usize>) -> String {
+    let text = match decimals {
+        Some(decimals) => format!("{:.*}", decimals, value),
+        None => value.to_string(),
+    };
+    let (sign, text) = match text.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", text.as_str()),
+    };
+    let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+    let fraction = fraction.trim_end_matches('0');
+
+    let mut grouped = String::new();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    // 四舍五入后为 0 时不显示负号
+    let sign = if grouped.chars().all(|c| c == '0' || c == ',') && fraction.is_empty() { "" } else { sign };
+    if fraction.is_empty() {
+        format!("{}{}", sign, grouped)
+    } else {
+        format!("{}{}.{}", sign, grouped, fraction)
+    }
+}
+
+/// 按交易对的价格精度格式化
+pub fn format_price(value: f64, filters: Option<&SymbolFilters>) -> String {
+    format_number(value, filters.map(|filters| decimals(filters.tick_size)))
+}
+
+/// 按交易对的数量精度格式化
+pub fn format_quantity(value: f64, filters: Option<&SymbolFilters>) -> String {
+    format_number(value, filters.map(|filters| decimals(filters.step_size)))
+}
+
+/// 涨跌额，上涨时带 `+` 号
+pub fn format_change(value: f64, filters: Option<&SymbolFilters>) -> String {
+    let text = format_price(value, filters);
+    if value > 0.0 {
+        format!("+{}", text)
+    } else {
+        text
+    }
+}
+
+/// 涨跌幅，保留两位小数并带正负号
+pub fn format_percent(value: f64) -> String {
+    format!("{:+.2}%", value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decimals_follow_step_size() {
+        assert_eq!(decimals(0.01), 2);
+        assert_eq!(decimals(0.00001), 5);
+        assert_eq!(decimals(1.0), 0);
+        assert_eq!(decimals(10.0), 0);
+        assert_eq!(decimals(0.0), 0);
+    }
+
+    #[test]
+    fn formats_with_precision_separators_and_trimmed_zeros() {
+        assert_eq!(format_number(67000.1, Some(2)), "67,000.1");
+        assert_eq!(format_number(1234567.0, Some(2)), "1,234,567");
+        assert_eq!(format_number(0.00001234, Some(8)), "0.00001234");
+        assert_eq!(format_number(0.000012345, Some(7)), "0.0000123");
+        assert_eq!(format_number(-1234.5, None), "-1,234.5");
+        assert_eq!(format_number(999.999, Some(2)), "1,000");
+        assert_eq!(format_number(-0.001, Some(2)), "0");
+        assert_eq!(format_number(123.0, None), "123");
+    }
+
+    #[test]
+    fn formats_change_with_sign() {
+        let filters = SymbolFilters {
+            tick_size: 0.01,
+            step_size: 0.001,
+            ..SymbolFilters::default()
+        };
+        assert_eq!(format_change(1234.5, Some(&filters)), "+1,234.5");
+        assert_eq!(format_change(-0.5, Some(&filters)), "-0.5");
+        assert_eq!(format_quantity(1.23456, Some(&filters)), "1.235");
+        assert_eq!(format_percent(1.2), "+1.20%");
+        assert_eq!(format_percent(-0.456), "-0.46%");
+    }
+}
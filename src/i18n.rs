@@ -0,0 +1,391 @@
+//! 界面文字的多语言支持。
+//!
+//! 源码中的中文文本即消息键：`tr` 在英文界面下从 `CATALOG` 查出对应译文，
+//! 带参数的文本用 `{}` 占位，由 `trf` 翻译后依次填入参数。
+//! 日期格式和大数字的单位也随语言变化。
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 界面语言，`auto` 时根据环境变量检测
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    Auto,
+    Zh,
+    En,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::Auto, Language::Zh, Language::En];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::Auto => "auto",
+            Language::Zh => "zh",
+            Language::En => "en",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|language| language.name() == name)
+    }
+
+    /// 把 `auto` 解析为具体语言：依次查看 `LC_ALL`、`LC_MESSAGES`、`LANG`，
+    /// 第一个非空的值以 `zh` 开头时为中文，否则为英文；都未设置时为中文
+    pub fn resolve(self) -> Language {
+        if self != Language::Auto {
+            return self;
+        }
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty());
+        match locale {
+            Some(locale) if !locale.starts_with("zh") => Language::En,
+            _ => Language::Zh,
+        }
+    }
+}
+
+static ENGLISH: AtomicBool = AtomicBool::new(false);
+
+/// 设置界面语言，之后的 `tr` / `trf` 调用立即生效
+pub fn set_language(language: Language) {
+    ENGLISH.store(language.resolve() == Language::En, Ordering::Relaxed);
+}
+
+/// 当前生效的界面语言（不会是 `auto`）
+pub fn current() -> Language {
+    if ENGLISH.load(Ordering::Relaxed) {
+        Language::En
+    } else {
+        Language::Zh
+    }
+}
+
+/// 翻译一条文本，目录中缺少译文时原样返回
+pub fn tr(text: &'static str) -> &'static str {
+    if current() == Language::Zh {
+        return text;
+    }
+    CATALOG
+        .iter()
+        .find(|(zh, _)| *zh == text)
+        .map_or(text, |(_, en)| en)
+}
+
+/// 翻译带 `{}` 占位的文本并依次填入参数
+pub fn trf(template: &'static str, args: &[&dyn Display]) -> String {
+    let mut result = String::new();
+    let mut args = args.iter();
+    let mut parts = tr(template).split("{}");
+    if let Some(first) = parts.next() {
+        result.push_str(first);
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            result.push_str(&arg.to_string());
+        }
+        result.push_str(part);
+    }
+    result
+}
+
+/// 日期时间的显示粒度
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateFormat {
+    /// 年月日
+    Date,
+    /// 月日和时分
+    DayTime,
+    /// 年月日和时分
+    DateTime,
+    /// 时分
+    Time,
+    /// 时分秒
+    TimeSeconds,
+}
+
+/// 当前语言下 `chrono` 的格式字符串
+pub fn date_format(format: DateFormat) -> &'static str {
+    let english = current() == Language::En;
+    match format {
+        DateFormat::Date if english => "%b %-d, %Y",
+        DateFormat::Date => "%Y-%m-%d",
+        DateFormat::DayTime if english => "%b %-d %H:%M",
+        DateFormat::DayTime => "%m-%d %H:%M",
+        DateFormat::DateTime if english => "%b %-d, %Y %H:%M",
+        DateFormat::DateTime => "%Y-%m-%d %H:%M",
+        DateFormat::Time => "%H:%M",
+        DateFormat::TimeSeconds => "%H:%M:%S",
+    }
+}
+
+/// 把毫秒时间戳按本地时区和当前语言格式化
+pub fn format_timestamp(millis: i64, format: DateFormat) -> String {
+    chrono::DateTime::from_timestamp_millis(millis)
+        .map(|time| time.with_timezone(&chrono::Local).format(date_format(format)).to_string())
+        .unwrap_or_default()
+}
+
+/// 大数字的紧凑写法，如 `1.2M`、`3.45B`：中文使用万、亿，英文使用 K、M、B
+pub fn format_compact(value: f64) -> String {
+    let units: &[(f64, &str)] = match current() {
+        Language::En => &[(1e9, "B"), (1e6, "M"), (1e3, "K")],
+        _ => &[(1e8, "亿"), (1e4, "万")],
+    };
+    let trim = |text: String| text.trim_end_matches('0').trim_end_matches('.').to_string();
+    for (scale, unit) in units {
+        if value.abs() >= *scale {
+            return format!("{}{}", trim(format!("{:.2}", value / scale)), unit);
+        }
+    }
+    trim(format!("{:.2}", value))
+}
+
+/// 中文文本到英文译文的对照表
+const CATALOG: &[(&str, &str)] = &[
+    ("按键配置", "Key bindings"),
+    ("配置文件", "Config file"),
+    ("保存迁移后的配置失败: {}", "Failed to save migrated config: {}"),
+    ("主题配置", "Theme"),
+    ("已到达最早的K线", "Reached the earliest candle"),
+    ("配置已保存", "Configuration saved"),
+    ("对数", "logarithmic"),
+    ("线性", "linear"),
+    ("已关闭对比", "Comparison off"),
+    ("添加失败: 可能已存在或超过最大数量", "Add failed: already listed or too many pairs"),
+    ("已恢复默认交易对", "Restored default pairs"),
+    ("加载历史K线失败: {}", "Failed to load history: {}"),
+    ("获取 {} 对比数据失败: {}", "Failed to fetch comparison data for {}: {}"),
+    ("获取 {} 订单簿失败: {}", "Failed to fetch order book for {}: {}"),
+    ("获取 {} 最近成交失败: {}", "Failed to fetch recent trades for {}: {}"),
+    ("刷新数据失败: {}", "Refresh failed: {}"),
+    ("自动刷新数据失败: {}", "Auto refresh failed: {}"),
+    ("{} K线周期: {}", "{} interval: {}"),
+    ("{} 图表样式: {}", "{} chart style: {}"),
+    ("{} (当前 {})", "{} (now {})"),
+    ("价格提醒: {}", "Price alert: {}"),
+    ("保存配置失败: {}", "Failed to save configuration: {}"),
+    ("价格坐标: {}", "Price scale: {}"),
+    ("深度图范围: ±{}%", "Depth range: ±{}%"),
+    ("交易对不存在: {}", "Unknown pair: {}"),
+    ("已添加价格提醒: {}", "Price alert added: {}"),
+    ("对比: {}", "Comparing: {}"),
+    ("未知的主题: {}", "Unknown theme: {}"),
+    ("主题: {}", "Theme: {}"),
+    ("界面语言: {}", "Language: {}"),
+    ("图表网格: {}", "Chart grid: {}"),
+    ("已添加交易对: {}", "Added pair: {}"),
+    ("已删除交易对: {}", "Removed pair: {}"),
+    ("补全: {}", "Completions: {}"),
+    ("请输入命令", "Enter a command"),
+    ("无效的K线周期: {} (可用: {})", "Invalid interval: {} (available: {})"),
+    ("用法: :interval <周期>，如 :interval 1h", "Usage: :interval <interval>, e.g. :interval 1h"),
+    (
+        "用法: :theme <主题名或涨跌配色>，如 :theme light、:theme red_up",
+        "Usage: :theme <preset or price colors>, e.g. :theme light, :theme red_up",
+    ),
+    ("用法: :language auto|zh|en", "Usage: :language auto|zh|en"),
+    ("未知的语言: {} (可用: auto zh en)", "Unknown language: {} (available: auto zh en)"),
+    ("用法: :alert <交易对> >|< <价格>", "Usage: :alert <pair> >|< <price>"),
+    ("未知命令: {}", "Unknown command: {}"),
+    ("用法: :{} <交易对>...", "Usage: :{} <pair>..."),
+    ("无效的交易对: {}", "Invalid pair: {}"),
+    ("无效的价格: {}", "Invalid price: {}"),
+    ("蜡烛图", "Candles"),
+    ("美国线", "OHLC bars"),
+    ("折线图", "Line"),
+    ("面积图", "Area"),
+    ("平均K线", "Heikin-Ashi"),
+    ("退出", "Quit"),
+    ("刷新", "Refresh"),
+    ("上一个", "Up"),
+    ("下一个", "Down"),
+    ("上移交易对", "Move pair up"),
+    ("下移交易对", "Move pair down"),
+    ("快速选择", "Quick select"),
+    ("选择第1个", "Select 1st"),
+    ("选择第2个", "Select 2nd"),
+    ("选择第3个", "Select 3rd"),
+    ("选择第4个", "Select 4th"),
+    ("选择第5个", "Select 5th"),
+    ("添加交易对", "Add pair"),
+    ("删除交易对", "Remove pair"),
+    ("恢复默认", "Restore defaults"),
+    ("保存配置", "Save"),
+    ("命令", "Command"),
+    ("缩短周期", "Shorter interval"),
+    ("延长周期", "Longer interval"),
+    ("向前平移", "Pan left"),
+    ("向后平移", "Pan right"),
+    ("放大", "Zoom in"),
+    ("缩小", "Zoom out"),
+    ("回到最新", "Latest"),
+    ("十字光标", "Crosshair"),
+    ("成交量", "Volume"),
+    ("增高成交量", "Taller volume"),
+    ("降低成交量", "Shorter volume"),
+    ("均线", "Moving averages"),
+    ("布林带", "Bollinger"),
+    ("随机指标", "Stochastic"),
+    ("图表样式", "Chart style"),
+    ("对数坐标", "Log scale"),
+    ("图表网格", "Chart grid"),
+    ("下一个图表", "Next chart"),
+    ("上一个图表", "Previous chart"),
+    ("深度图", "Depth chart"),
+    ("深度范围", "Depth range"),
+    ("帮助", "Help"),
+    ("详情", "Details"),
+    ("关闭详情", "Close details"),
+    ("详情页中不能修改交易对列表，按 {} 返回", "The pair list cannot be edited in the detail view, press {} to go back"),
+    ("帮助 ({}/{})", "Help ({}/{})"),
+    ("未绑定", "unbound"),
+    ("正常模式", "Normal mode"),
+    ("图表", "Chart"),
+    ("命令行", "Command line"),
+    ("帮助窗口", "Help window"),
+    ("滚轮", "Wheel"),
+    ("在图表上缩放，在列表上移动选中项", "Zoom over the chart, move the selection over the list"),
+    ("拖动", "Drag"),
+    ("平移查看历史K线", "Pan through history"),
+    ("点击", "Click"),
+    ("选中交易对或切换网格焦点", "Select a pair or focus a grid cell"),
+    ("执行", "Run"),
+    ("取消", "Cancel"),
+    ("确认", "Confirm"),
+    ("删除字符", "Delete character"),
+    ("补全，连续按下时切换候选", "Complete, press again to cycle candidates"),
+    ("浏览历史命令", "Browse history"),
+    ("删除字符，输入为空时退出", "Delete character, leave when empty"),
+    (":<操作名>", ":<action>"),
+    ("执行同名操作，如 :refresh", "Run the action of that name, e.g. :refresh"),
+    ("滚动", "Scroll"),
+    ("翻页", "Page"),
+    ("回到顶部", "Top"),
+    ("关闭", "Close"),
+    ("<交易对>...", "<pair>..."),
+    ("[交易对]...", "[pair]..."),
+    ("<周期>", "<interval>"),
+    ("<交易对> >|< <价格>", "<pair> >|< <price>"),
+    ("<主题名或涨跌配色>", "<preset or price colors>"),
+    ("切换当前交易对的K线周期", "Change the interval of the current pair"),
+    ("添加价格提醒", "Add a price alert"),
+    ("切换主题或涨跌配色", "Change theme or price colors"),
+    ("切换界面语言", "Change interface language"),
+    ("对比涨跌幅，不带参数时关闭", "Compare performance, no arguments to turn off"),
+    ("保存后退出", "Save and quit"),
+    ("未知操作: {}", "Unknown action: {}"),
+    ("无法解析按键 '{}' ({})", "Cannot parse key '{}' ({})"),
+    ("按键冲突: {} 已绑定到 {}，忽略 {}", "Key conflict: {} is bound to {}, ignoring {}"),
+    ("无效的颜色 {}，应为 #rrggbb", "Invalid color {}, expected #rrggbb"),
+    ("未知的颜色 {}", "Unknown color {}"),
+    ("加载中...", "Loading..."),
+    ("终端太小: {}×{}，至少需要 {}×{}", "Terminal too small: {}×{}, need at least {}×{}"),
+    ("交易对", "Pair"),
+    ("涨跌幅", "Change"),
+    ("价格", "Price"),
+    ("24h走势", "24h trend"),
+    ("交易对列表", "Pairs"),
+    ("按 'A' 添加交易对", "Press 'A' to add a pair"),
+    ("交易对输入", "Pair input"),
+    ("添加交易对: {}_", "Add pair: {}_"),
+    ("Enter:确认  Esc:取消  Backspace:删除字符", "Enter:Confirm  Esc:Cancel  Backspace:Delete"),
+    ("Enter:执行  Esc:取消  Tab:补全  ↑↓:历史  命令: {}", "Enter:Run  Esc:Cancel  Tab:Complete  ↑↓:History  Commands: {}"),
+    ("↑↓/jk:滚动  PgUp/PgDn:翻页  Home:顶部  Esc/q/?:关闭", "↑↓/jk:Scroll  PgUp/PgDn:Page  Home:Top  Esc/q/?:Close"),
+    ("请选择一个交易对查看K线图", "Select a pair to view its chart"),
+    ("K线图", "Chart"),
+    ("K线图 - {} ({}周期 · {})", "Chart - {} ({} · {})"),
+    ("加载K线数据中...", "Loading candles..."),
+    ("暂无K线数据", "No candles"),
+    ("交易对信息", "Pair info"),
+    (
+        "{} | 价格: {} | 涨跌: {} ({}) | 24h高: {} | 24h低: {} | 24h量: {}",
+        "{} | Price: {} | Change: {} ({}) | 24h high: {} | 24h low: {} | 24h vol: {}",
+    ),
+    ("{} | 加载价格信息中...", "{} | Loading price..."),
+    ("K线数据", "Candle"),
+    ("{} | 开: {} | 高: {} | 低: {} | 收: {} | 量: {} | 涨跌: {}", "{} | O: {} | H: {} | L: {} | C: {} | V: {} | Change: {}"),
+    ("24h高", "24h H"),
+    ("24h低", "24h L"),
+    ("─ 成交量: {}", "─ Volume: {}"),
+    ("深度图 - {} (±{}%)", "Depth - {} (±{}%)"),
+    ("买一: {} ({})  ", "Bid: {} ({})  "),
+    ("卖一: {} ({})  ", "Ask: {} ({})  "),
+    ("价差: {}", "Spread: {}"),
+    ("24小时行情", "24h statistics"),
+    ("交易规则", "Trading rules"),
+    ("最新价", "Last"),
+    ("涨跌", "Change"),
+    ("开盘价", "Open"),
+    ("最高价", "High"),
+    ("最低价", "Low"),
+    ("加权均价", "Weighted avg"),
+    ("成交额", "Quote volume"),
+    ("成交笔数", "Trades"),
+    ("买一", "Bid"),
+    ("卖一", "Ask"),
+    ("价差", "Spread"),
+    ("状态", "Status"),
+    ("资产", "Assets"),
+    ("价格步长", "Tick size"),
+    ("数量步长", "Step size"),
+    ("价格范围", "Price range"),
+    ("数量范围", "Quantity range"),
+    ("最小成交额", "Min notional"),
+    ("最近成交", "Recent trades"),
+    ("时间", "Time"),
+    ("数量", "Qty"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 源码中所有 `tr("...")` / `trf("...", ...)` 的文本都需要有译文
+    #[test]
+    fn catalog_covers_all_messages() {
+        let sources = [
+            include_str!("api.rs"),
+            include_str!("app.rs"),
+            include_str!("command.rs"),
+            include_str!("config.rs"),
+            include_str!("keymap.rs"),
+            include_str!("main.rs"),
+            include_str!("theme.rs"),
+            include_str!("ui.rs"),
+        ];
+        let mut missing = Vec::new();
+        for source in sources {
+            for call in ["tr(", "trf("] {
+                for (start, _) in source.match_indices(call) {
+                    // 跳过 `str(` 之类的其他函数，文本可以换行写在括号后
+                    let before = source[..start].chars().next_back();
+                    if before.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                        continue;
+                    }
+                    let Some(rest) = source[start + call.len()..].trim_start().strip_prefix('"') else {
+                        continue;
+                    };
+                    let text = &rest[..rest.find('"').unwrap()];
+                    if !CATALOG.iter().any(|(zh, _)| *zh == text) {
+                        missing.push(text);
+                    }
+                }
+            }
+        }
+        assert!(missing.is_empty(), "缺少译文: {:?}", missing);
+    }
+
+    #[test]
+    fn catalog_keys_are_unique_and_keep_placeholders() {
+        for (i, (zh, en)) in CATALOG.iter().enumerate() {
+            assert!(!CATALOG[..i].iter().any(|(other, _)| other == zh), "重复: {}", zh);
+            assert_eq!(zh.matches("{}").count(), en.matches("{}").count(), "占位符不一致: {}", zh);
+        }
+    }
+}
@@ -0,0 +1,359 @@
+//! 基于K线数据计算的技术指标。
+//!
+//! 所有函数返回与输入等长的序列，数据不足以计算的位置为 `None`，
+//! 这样结果可以直接按下标与K线对齐。
+
+/// 简单移动平均 (SMA)
+pub fn sma(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; values.len()];
+    if period == 0 || values.len() < period {
+        return result;
+    }
+
+    let mut sum: f64 = values[..period].iter().sum();
+    result[period - 1] = Some(sum / period as f64);
+    for i in period..values.len() {
+        sum += values[i] - values[i - period];
+        result[i] = Some(sum / period as f64);
+    }
+    result
+}
+
+/// 指数移动平均 (EMA)，以前 `period` 个值的 SMA 作为初始值
+pub fn ema(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; values.len()];
+    if period == 0 || values.len() < period {
+        return result;
+    }
+
+    let alpha = 2.0 / (period as f64 + 1.0);
+    let mut current = values[..period].iter().sum::<f64>() / period as f64;
+    result[period - 1] = Some(current);
+    for i in period..values.len() {
+        current = alpha * values[i] + (1.0 - alpha) * current;
+        result[i] = Some(current);
+    }
+    result
+}
+
+/// 加权移动平均 (WMA)，越新的值权重越大（权重 1..=period）
+pub fn wma(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; values.len()];
+    if period == 0 || values.len() < period {
+        return result;
+    }
+
+    let weight_sum = (period * (period + 1) / 2) as f64;
+    for i in period - 1..values.len() {
+        let window = &values[i + 1 - period..=i];
+        let weighted: f64 = window
+            .iter()
+            .enumerate()
+            .map(|(w, v)| (w + 1) as f64 * v)
+            .sum();
+        result[i] = Some(weighted / weight_sum);
+    }
+    result
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BollingerPoint {
+    pub upper: f64,
+    pub middle: f64,
+    pub lower: f64,
+}
+
+/// 布林带：中轨为 SMA，上下轨为中轨加减 `std_dev` 倍总体标准差
+pub fn bollinger(values: &[f64], period: usize, std_dev: f64) -> Vec<Option<BollingerPoint>> {
+    sma(values, period)
+        .into_iter()
+        .enumerate()
+        .map(|(i, middle)| {
+            let middle = middle?;
+            let window = &values[i + 1 - period..=i];
+            let variance = window.iter().map(|v| (v - middle).powi(2)).sum::<f64>() / period as f64;
+            let width = variance.sqrt() * std_dev;
+            Some(BollingerPoint {
+                upper: middle + width,
+                middle,
+                lower: middle - width,
+            })
+        })
+        .collect()
+}
+
+/// 相对强弱指数 (RSI)，使用 Wilder 平滑
+pub fn rsi(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; values.len()];
+    if period == 0 || values.len() <= period {
+        return result;
+    }
+
+    let change = |i: usize| values[i] - values[i - 1];
+    let mut avg_gain = (1..=period).map(|i| change(i).max(0.0)).sum::<f64>() / period as f64;
+    let mut avg_loss = (1..=period).map(|i| (-change(i)).max(0.0)).sum::<f64>() / period as f64;
+    let to_rsi = |gain: f64, loss: f64| {
+        if loss == 0.0 {
+            if gain == 0.0 { 50.0 } else { 100.0 }
+        } else {
+            100.0 - 100.0 / (1.0 + gain / loss)
+        }
+    };
+
+    result[period] = Some(to_rsi(avg_gain, avg_loss));
+    for (i, slot) in result.iter_mut().enumerate().skip(period + 1) {
+        avg_gain = (avg_gain * (period - 1) as f64 + change(i).max(0.0)) / period as f64;
+        avg_loss = (avg_loss * (period - 1) as f64 + (-change(i)).max(0.0)) / period as f64;
+        *slot = Some(to_rsi(avg_gain, avg_loss));
+    }
+    result
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MacdPoint {
+    pub macd: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+/// MACD：快慢 EMA 之差为 MACD 线，其 EMA 为信号线，两者之差为柱状图
+pub fn macd(values: &[f64], fast: usize, slow: usize, signal: usize) -> Vec<Option<MacdPoint>> {
+    let fast_ema = ema(values, fast);
+    let slow_ema = ema(values, slow);
+    let macd_line: Vec<Option<f64>> = fast_ema
+        .iter()
+        .zip(&slow_ema)
+        .map(|(fast, slow)| Some((*fast)? - (*slow)?))
+        .collect();
+
+    // 信号线只在 MACD 线有值的部分上计算，再按下标对齐回去
+    let start = macd_line.iter().position(Option::is_some).unwrap_or(values.len());
+    let defined: Vec<f64> = macd_line[start..].iter().flatten().copied().collect();
+    let signal_line = ema(&defined, signal);
+
+    let mut result = vec![None; values.len()];
+    for (offset, signal) in signal_line.into_iter().enumerate() {
+        if let (Some(macd), Some(signal)) = (macd_line[start + offset], signal) {
+            result[start + offset] = Some(MacdPoint {
+                macd,
+                signal,
+                histogram: macd - signal,
+            });
+        }
+    }
+    result
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StochasticPoint {
+    pub k: f64,
+    pub d: f64,
+}
+
+/// 随机指标：原始 %K 经 `k_smooth` 周期 SMA 平滑为 %K，%K 的 `d_period` 周期 SMA 为 %D
+pub fn stochastic(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    k_period: usize,
+    k_smooth: usize,
+    d_period: usize,
+) -> Vec<Option<StochasticPoint>> {
+    let len = closes.len().min(highs.len()).min(lows.len());
+    let mut result = vec![None; len];
+    if k_period == 0 || len < k_period {
+        return result;
+    }
+
+    let raw_k: Vec<f64> = (k_period - 1..len)
+        .map(|i| {
+            let range = i + 1 - k_period..=i;
+            let highest = highs[range.clone()].iter().fold(f64::MIN, |a, b| a.max(*b));
+            let lowest = lows[range].iter().fold(f64::MAX, |a, b| a.min(*b));
+            if highest > lowest {
+                (closes[i] - lowest) / (highest - lowest) * 100.0
+            } else {
+                // 区间内价格不变时取中值
+                50.0
+            }
+        })
+        .collect();
+    let k = sma(&raw_k, k_smooth);
+    let k_defined_from = k.iter().position(Option::is_some).unwrap_or(k.len());
+    let k_values: Vec<f64> = k.iter().flatten().copied().collect();
+    let d = sma(&k_values, d_period);
+
+    for (offset, d) in d.into_iter().enumerate() {
+        let index = k_period - 1 + k_defined_from + offset;
+        if let (Some(k), Some(d)) = (k[k_defined_from + offset], d) {
+            result[index] = Some(StochasticPoint { k, d });
+        }
+    }
+    result
+}
+
+/// 把 `(开盘时间, 收盘价)` 序列按开盘时间对齐到 `times`，换算为相对第一个有效价格的涨跌百分比。
+/// 缺失的K线沿用前一根的收盘价，第一根有效K线之前和最后一根之后为 `None`。
+pub fn rebased_percent(times: &[i64], series: &[(i64, f64)]) -> Vec<Option<f64>> {
+    let mut result = Vec::with_capacity(times.len());
+    let mut next = 0;
+    let mut last = None;
+    let mut base = None;
+    let end = series.last().map(|(time, _)| *time);
+    for time in times {
+        // 没有获取到的K线不能当作价格不变，否则会画出一段水平线
+        if end.is_some_and(|end| *time > end) {
+            result.push(None);
+            continue;
+        }
+        while next < series.len() && series[next].0 <= *time {
+            last = Some(series[next].1);
+            next += 1;
+        }
+        if base.is_none() {
+            base = last.filter(|price| *price > 0.0);
+        }
+        result.push(base.zip(last).map(|(base, price)| (price / base - 1.0) * 100.0));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_series(actual: &[Option<f64>], expected: &[f64], tolerance: f64) {
+        let actual: Vec<f64> = actual.iter().flatten().copied().collect();
+        assert_eq!(actual.len(), expected.len(), "actual: {:?}", actual);
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() <= tolerance, "{} != {} (actual: {:?})", a, e, actual);
+        }
+    }
+
+    // StockCharts "Moving Averages" 示例中的 10 日收盘价
+    const EMA_CLOSES: [f64; 30] = [
+        22.27, 22.19, 22.08, 22.17, 22.18, 22.13, 22.23, 22.43, 22.24, 22.29, 22.15, 22.39, 22.38,
+        22.61, 23.36, 24.05, 23.75, 23.83, 23.95, 23.63, 23.82, 23.87, 23.65, 23.19, 23.10, 23.33,
+        22.68, 23.10, 22.40, 22.17,
+    ];
+
+    #[test]
+    fn sma_matches_reference() {
+        let result = sma(&EMA_CLOSES, 10);
+        assert!(result[..9].iter().all(Option::is_none));
+        assert_series(
+            &result[9..12],
+            &[22.22, 22.21, 22.23],
+            0.005,
+        );
+    }
+
+    #[test]
+    fn ema_matches_reference() {
+        let result = ema(&EMA_CLOSES, 10);
+        assert!(result[..9].iter().all(Option::is_none));
+        assert_series(
+            &result,
+            &[
+                22.22, 22.21, 22.24, 22.27, 22.33, 22.52, 22.80, 22.97, 23.13, 23.28, 23.34, 23.43,
+                23.51, 23.53, 23.47, 23.40, 23.39, 23.26, 23.23, 23.08, 22.92,
+            ],
+            0.01,
+        );
+    }
+
+    #[test]
+    fn wma_weights_recent_values_more() {
+        let result = wma(&[1.0, 2.0, 3.0, 4.0, 5.0], 3);
+        assert_series(&result, &[14.0 / 6.0, 20.0 / 6.0, 26.0 / 6.0], 1e-9);
+    }
+
+    #[test]
+    fn bollinger_uses_population_standard_deviation() {
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let bands = bollinger(&values, 8, 2.0);
+        let last = bands[7].unwrap();
+        assert!((last.middle - 5.0).abs() < 1e-9);
+        assert!((last.upper - 9.0).abs() < 1e-9);
+        assert!((last.lower - 1.0).abs() < 1e-9);
+        assert!(bands[6].is_none());
+    }
+
+    #[test]
+    fn rsi_matches_wilder_reference() {
+        // StockCharts "RSI" 示例，14 周期
+        let closes = [
+            44.3389, 44.0902, 44.1497, 43.6124, 44.3278, 44.8264, 45.0955, 45.4245, 45.8433,
+            46.0826, 45.8931, 46.0328, 45.6140, 46.2820, 46.2820, 46.0028, 46.0328, 46.4116,
+            46.2222, 45.6439, 46.2122, 46.2521, 45.7137, 46.4515, 45.7835, 45.3548, 44.0288,
+            44.1783, 44.2181, 44.5672, 43.4205, 42.6628, 43.1314,
+        ];
+        let result = rsi(&closes, 14);
+        assert!(result[..14].iter().all(Option::is_none));
+        assert_series(
+            &result,
+            &[
+                70.53, 66.32, 66.55, 69.41, 66.36, 57.97, 62.93, 63.26, 56.06, 62.38, 54.71, 50.42,
+                39.99, 41.46, 41.87, 45.46, 37.30, 33.08, 37.77,
+            ],
+            0.01,
+        );
+    }
+
+    #[test]
+    fn rsi_of_flat_and_rising_series() {
+        assert_eq!(rsi(&[1.0; 5], 3)[4], Some(50.0));
+        assert_eq!(rsi(&[1.0, 2.0, 3.0, 4.0, 5.0], 3)[4], Some(100.0));
+    }
+
+    #[test]
+    fn macd_of_linear_series_is_constant_lag_difference() {
+        // 线性序列的 EMA 恰好滞后 (n - 1) / 2，因此 MACD 线恒为 (26 - 12) / 2
+        let values: Vec<f64> = (0..60).map(|i| i as f64).collect();
+        let result = macd(&values, 12, 26, 9);
+        let first = result.iter().position(Option::is_some).unwrap();
+        assert_eq!(first, 25 + 8);
+        for point in result.iter().flatten() {
+            assert!((point.macd - 7.0).abs() < 1e-9);
+            assert!((point.signal - 7.0).abs() < 1e-9);
+            assert!(point.histogram.abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn stochastic_position_within_range() {
+        let highs = [3.0, 4.0, 5.0, 6.0, 7.0];
+        let lows = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let closes = [2.0, 3.0, 4.0, 6.0, 5.0];
+        let result = stochastic(&highs, &lows, &closes, 3, 1, 1);
+        assert!(result[..2].iter().all(Option::is_none));
+        // (4 - 1) / (5 - 1), (6 - 2) / (6 - 2), (5 - 3) / (7 - 3)
+        let k: Vec<f64> = result.iter().flatten().map(|p| p.k).collect();
+        assert_eq!(k, vec![75.0, 100.0, 50.0]);
+
+        let smoothed = stochastic(&highs, &lows, &closes, 3, 1, 3);
+        let last = smoothed[4].unwrap();
+        assert!((last.d - 75.0).abs() < 1e-9);
+        assert!(smoothed[3].is_none());
+    }
+
+    #[test]
+    fn rebased_percent_aligns_by_open_time() {
+        let times = [100, 200, 300, 400];
+        // 缺少 300 的K线，且 100 之前没有数据
+        let series = [(200, 50.0), (400, 60.0)];
+        let result = rebased_percent(&times, &series);
+        assert!(result[0].is_none());
+        assert_series(&result, &[0.0, 0.0, 20.0], 1e-9);
+
+        let series = [(100, 10.0), (200, 11.0), (300, 9.0), (400, 10.0)];
+        let result = rebased_percent(&times, &series);
+        assert_series(&result, &[0.0, 10.0, -10.0, 0.0], 1e-9);
+
+        // 最后一根K线之后没有数据，不沿用收盘价
+        let series = [(100, 10.0), (200, 11.0)];
+        let result = rebased_percent(&times, &series);
+        assert!(result[2].is_none() && result[3].is_none());
+        assert_series(&result, &[0.0, 10.0], 1e-9);
+    }
+}
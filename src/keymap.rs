@@ -0,0 +1,574 @@
+use crate::i18n::{tr, trf};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// 正常模式下可绑定按键的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Refresh,
+    SelectUp,
+    SelectDown,
+    MovePairUp,
+    MovePairDown,
+    Select1,
+    Select2,
+    Select3,
+    Select4,
+    Select5,
+    AddPair,
+    RemovePair,
+    RestoreDefaultPairs,
+    SaveConfig,
+    CommandMode,
+    PrevInterval,
+    NextInterval,
+    PanLeft,
+    PanRight,
+    ZoomIn,
+    ZoomOut,
+    JumpToLatest,
+    ToggleCrosshair,
+    ToggleVolume,
+    GrowVolume,
+    ShrinkVolume,
+    ToggleMovingAverages,
+    ToggleBollinger,
+    ToggleRsi,
+    ToggleMacd,
+    ToggleStochastic,
+    CycleChartStyle,
+    ToggleLogScale,
+    CycleGridLayout,
+    NextGridCell,
+    PrevGridCell,
+    ToggleDepthChart,
+    CycleDepthRange,
+    ShowHelp,
+    OpenDetail,
+    CloseDetail,
+}
+
+impl Action {
+    /// 所有操作，顺序即底部栏和帮助中的显示顺序
+    pub const ALL: [Action; 42] = [
+        Action::ShowHelp,
+        Action::Quit,
+        Action::Refresh,
+        Action::SelectUp,
+        Action::SelectDown,
+        Action::OpenDetail,
+        Action::CloseDetail,
+        Action::MovePairUp,
+        Action::MovePairDown,
+        Action::Select1,
+        Action::Select2,
+        Action::Select3,
+        Action::Select4,
+        Action::Select5,
+        Action::AddPair,
+        Action::RemovePair,
+        Action::RestoreDefaultPairs,
+        Action::SaveConfig,
+        Action::PrevInterval,
+        Action::NextInterval,
+        Action::PanLeft,
+        Action::PanRight,
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::JumpToLatest,
+        Action::ToggleCrosshair,
+        Action::ToggleVolume,
+        Action::GrowVolume,
+        Action::ShrinkVolume,
+        Action::ToggleMovingAverages,
+        Action::ToggleBollinger,
+        Action::ToggleRsi,
+        Action::ToggleMacd,
+        Action::ToggleStochastic,
+        Action::CycleChartStyle,
+        Action::ToggleLogScale,
+        Action::CycleGridLayout,
+        Action::NextGridCell,
+        Action::PrevGridCell,
+        Action::ToggleDepthChart,
+        Action::CycleDepthRange,
+        Action::CommandMode,
+    ];
+
+    /// 配置文件 `[keybindings]` 中使用的名称
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Refresh => "refresh",
+            Action::SelectUp => "select_up",
+            Action::SelectDown => "select_down",
+            Action::MovePairUp => "move_pair_up",
+            Action::MovePairDown => "move_pair_down",
+            Action::Select1 => "select_1",
+            Action::Select2 => "select_2",
+            Action::Select3 => "select_3",
+            Action::Select4 => "select_4",
+            Action::Select5 => "select_5",
+            Action::AddPair => "add_pair",
+            Action::RemovePair => "remove_pair",
+            Action::RestoreDefaultPairs => "restore_default_pairs",
+            Action::SaveConfig => "save_config",
+            Action::CommandMode => "command_mode",
+            Action::PrevInterval => "prev_interval",
+            Action::NextInterval => "next_interval",
+            Action::PanLeft => "pan_left",
+            Action::PanRight => "pan_right",
+            Action::ZoomIn => "zoom_in",
+            Action::ZoomOut => "zoom_out",
+            Action::JumpToLatest => "jump_to_latest",
+            Action::ToggleCrosshair => "toggle_crosshair",
+            Action::ToggleVolume => "toggle_volume",
+            Action::GrowVolume => "grow_volume",
+            Action::ShrinkVolume => "shrink_volume",
+            Action::ToggleMovingAverages => "toggle_moving_averages",
+            Action::ToggleBollinger => "toggle_bollinger",
+            Action::ToggleRsi => "toggle_rsi",
+            Action::ToggleMacd => "toggle_macd",
+            Action::ToggleStochastic => "toggle_stochastic",
+            Action::CycleChartStyle => "cycle_chart_style",
+            Action::ToggleLogScale => "toggle_log_scale",
+            Action::CycleGridLayout => "cycle_grid_layout",
+            Action::NextGridCell => "next_grid_cell",
+            Action::PrevGridCell => "prev_grid_cell",
+            Action::ToggleDepthChart => "toggle_depth_chart",
+            Action::CycleDepthRange => "cycle_depth_range",
+            Action::ShowHelp => "show_help",
+            Action::OpenDetail => "open_detail",
+            Action::CloseDetail => "close_detail",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Quit => tr("退出"),
+            Action::Refresh => tr("刷新"),
+            Action::SelectUp => tr("上一个"),
+            Action::SelectDown => tr("下一个"),
+            Action::MovePairUp => tr("上移交易对"),
+            Action::MovePairDown => tr("下移交易对"),
+            Action::Select1 => tr("选择第1个"),
+            Action::Select2 => tr("选择第2个"),
+            Action::Select3 => tr("选择第3个"),
+            Action::Select4 => tr("选择第4个"),
+            Action::Select5 => tr("选择第5个"),
+            Action::AddPair => tr("添加交易对"),
+            Action::RemovePair => tr("删除交易对"),
+            Action::RestoreDefaultPairs => tr("恢复默认"),
+            Action::SaveConfig => tr("保存配置"),
+            Action::CommandMode => tr("命令"),
+            Action::PrevInterval => tr("缩短周期"),
+            Action::NextInterval => tr("延长周期"),
+            Action::PanLeft => tr("向前平移"),
+            Action::PanRight => tr("向后平移"),
+            Action::ZoomIn => tr("放大"),
+            Action::ZoomOut => tr("缩小"),
+            Action::JumpToLatest => tr("回到最新"),
+            Action::ToggleCrosshair => tr("十字光标"),
+            Action::ToggleVolume => tr("成交量"),
+            Action::GrowVolume => tr("增高成交量"),
+            Action::ShrinkVolume => tr("降低成交量"),
+            Action::ToggleMovingAverages => tr("均线"),
+            Action::ToggleBollinger => tr("布林带"),
+            Action::ToggleRsi => "RSI",
+            Action::ToggleMacd => "MACD",
+            Action::ToggleStochastic => tr("随机指标"),
+            Action::CycleChartStyle => tr("图表样式"),
+            Action::ToggleLogScale => tr("对数坐标"),
+            Action::CycleGridLayout => tr("图表网格"),
+            Action::NextGridCell => tr("下一个图表"),
+            Action::PrevGridCell => tr("上一个图表"),
+            Action::ToggleDepthChart => tr("深度图"),
+            Action::CycleDepthRange => tr("深度范围"),
+            Action::ShowHelp => tr("帮助"),
+            Action::OpenDetail => tr("详情"),
+            Action::CloseDetail => tr("关闭详情"),
+        }
+    }
+
+    /// 快速选择类操作数量较多，不在底部栏中逐一列出
+    pub fn in_footer(self) -> bool {
+        self.select_index().is_none()
+    }
+
+    /// 作用于图表的操作，帮助中单独分组
+    pub fn is_chart(self) -> bool {
+        matches!(
+            self,
+            Action::PrevInterval
+                | Action::NextInterval
+                | Action::PanLeft
+                | Action::PanRight
+                | Action::ZoomIn
+                | Action::ZoomOut
+                | Action::JumpToLatest
+                | Action::ToggleCrosshair
+                | Action::ToggleVolume
+                | Action::GrowVolume
+                | Action::ShrinkVolume
+                | Action::ToggleMovingAverages
+                | Action::ToggleBollinger
+                | Action::ToggleRsi
+                | Action::ToggleMacd
+                | Action::ToggleStochastic
+                | Action::CycleChartStyle
+                | Action::ToggleLogScale
+                | Action::CycleGridLayout
+                | Action::NextGridCell
+                | Action::PrevGridCell
+                | Action::ToggleDepthChart
+                | Action::CycleDepthRange
+        )
+    }
+
+    /// 修改交易对列表的操作，详情页中不可用
+    pub fn edits_pairs(self) -> bool {
+        matches!(
+            self,
+            Action::AddPair
+                | Action::RemovePair
+                | Action::RestoreDefaultPairs
+                | Action::MovePairUp
+                | Action::MovePairDown
+        )
+    }
+
+    pub fn select_index(self) -> Option<usize> {
+        match self {
+            Action::Select1 => Some(0),
+            Action::Select2 => Some(1),
+            Action::Select3 => Some(2),
+            Action::Select4 => Some(3),
+            Action::Select5 => Some(4),
+            _ => None,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| action.name() == name)
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q"],
+            Action::Refresh => &["r", "space"],
+            Action::SelectUp => &["up"],
+            Action::SelectDown => &["down"],
+            Action::MovePairUp => &["shift+up"],
+            Action::MovePairDown => &["shift+down"],
+            Action::Select1 => &["1"],
+            Action::Select2 => &["2"],
+            Action::Select3 => &["3"],
+            Action::Select4 => &["4"],
+            Action::Select5 => &["5"],
+            Action::AddPair => &["a"],
+            Action::RemovePair => &["d"],
+            // 会丢弃整个自定义列表且无法撤销，默认不绑定按键，通过 `:restore_default_pairs` 执行
+            Action::RestoreDefaultPairs => &[],
+            Action::SaveConfig => &["s"],
+            Action::CommandMode => &[":"],
+            Action::PrevInterval => &["["],
+            Action::NextInterval => &["]"],
+            Action::PanLeft => &["left"],
+            Action::PanRight => &["right"],
+            Action::ZoomIn => &["+", "="],
+            Action::ZoomOut => &["-"],
+            Action::JumpToLatest => &["end"],
+            Action::ToggleCrosshair => &["c"],
+            Action::ToggleVolume => &["v"],
+            Action::GrowVolume => &["}"],
+            Action::ShrinkVolume => &["{"],
+            Action::ToggleMovingAverages => &["m"],
+            Action::ToggleBollinger => &["b"],
+            Action::ToggleRsi => &["R"],
+            Action::ToggleMacd => &["M"],
+            Action::ToggleStochastic => &["K"],
+            Action::CycleChartStyle => &["t"],
+            Action::ToggleLogScale => &["l"],
+            Action::CycleGridLayout => &["g"],
+            Action::NextGridCell => &["tab"],
+            Action::PrevGridCell => &["backtab"],
+            Action::ToggleDepthChart => &["o"],
+            Action::CycleDepthRange => &["O"],
+            Action::ShowHelp => &["?"],
+            Action::OpenDetail => &["enter"],
+            Action::CloseDetail => &["esc"],
+        }
+    }
+}
+
+/// 一个按键及其修饰键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// 字符键的大小写（以及 BackTab）已经体现了 Shift，统一去掉 Shift 修饰以便比较
+    fn normalized(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self { code, modifiers }
+    }
+
+    pub fn from_event(key: &KeyEvent) -> Self {
+        Self::normalized(key.code, key.modifiers)
+    }
+
+    /// 解析形如 `q`、`ctrl+r`、`shift+up`、`space` 的按键描述
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut parts: Vec<&str> = text.split('+').collect();
+        // 单独的 "+" 键会被拆成两个空串
+        let key = if text.ends_with("++") || text == "+" {
+            parts.truncate(parts.len().saturating_sub(2));
+            "+"
+        } else {
+            parts.pop()?
+        };
+
+        for part in parts {
+            match part.to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => modifiers |= KeyModifiers::SHIFT,
+                _ => return None,
+            }
+        }
+
+        let code = match key.to_lowercase().as_str() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "space" => KeyCode::Char(' '),
+            lower => {
+                if let Some(n) = lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    KeyCode::F(n)
+                } else {
+                    let mut chars = key.chars();
+                    let c = chars.next()?;
+                    if chars.next().is_some() {
+                        return None;
+                    }
+                    // shift+d 与 D 等价
+                    if modifiers.contains(KeyModifiers::SHIFT) && c.is_ascii_lowercase() {
+                        KeyCode::Char(c.to_ascii_uppercase())
+                    } else {
+                        KeyCode::Char(c)
+                    }
+                }
+            }
+        };
+
+        Some(Self::normalized(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            _ => write!(f, "?"),
+        }
+    }
+}
+
+/// 当前生效的按键映射
+pub struct KeyMap {
+    bindings: HashMap<KeyBinding, Action>,
+}
+
+impl KeyMap {
+    /// 根据配置中的 `[keybindings]` 构建映射，返回映射和加载时发现的问题。
+    /// 配置中出现的操作会完全替换其默认按键，用户配置优先于默认按键。
+    pub fn from_config(overrides: &BTreeMap<String, Vec<String>>) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        let mut user: Vec<(Action, KeyBinding)> = Vec::new();
+
+        for (name, keys) in overrides {
+            let Some(action) = Action::from_name(name) else {
+                warnings.push(trf("未知操作: {}", &[name]));
+                continue;
+            };
+            for key in keys {
+                match KeyBinding::parse(key) {
+                    Some(binding) => user.push((action, binding)),
+                    None => warnings.push(trf("无法解析按键 '{}' ({})", &[key, name])),
+                }
+            }
+        }
+
+        let defaults = Action::ALL
+            .into_iter()
+            .filter(|action| !overrides.contains_key(action.name()))
+            .flat_map(|action| {
+                action
+                    .default_keys()
+                    .iter()
+                    .filter_map(move |key| KeyBinding::parse(key).map(|binding| (action, binding)))
+            });
+
+        let mut bindings = HashMap::new();
+        for (action, binding) in user.into_iter().chain(defaults) {
+            match bindings.get(&binding) {
+                Some(existing) if *existing != action => {
+                    warnings.push(trf(
+                        "按键冲突: {} 已绑定到 {}，忽略 {}",
+                        &[&binding, &Action::name(*existing), &action.name()],
+                    ));
+                }
+                _ => {
+                    bindings.insert(binding, action);
+                }
+            }
+        }
+
+        (Self { bindings }, warnings)
+    }
+
+    pub fn action_for(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings.get(&KeyBinding::from_event(key)).copied()
+    }
+
+    /// 绑定到某个操作的所有按键，按显示文本排序
+    pub fn keys_for(&self, action: Action) -> Vec<KeyBinding> {
+        let mut keys: Vec<KeyBinding> = self
+            .bindings
+            .iter()
+            .filter(|(_, a)| **a == action)
+            .map(|(key, _)| *key)
+            .collect();
+        keys.sort_by_key(|key| key.to_string());
+        keys
+    }
+
+    /// 用于界面显示的按键文本，如 `r/Space`
+    pub fn describe(&self, action: Action) -> Option<String> {
+        let keys = self.keys_for(action);
+        if keys.is_empty() {
+            return None;
+        }
+        Some(keys.iter().map(|key| key.to_string()).collect::<Vec<_>>().join("/"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyBinding {
+        KeyBinding { code, modifiers }
+    }
+
+    #[test]
+    fn parses_keys_with_modifiers() {
+        let cases = [
+            ("q", Some(key(KeyCode::Char('q'), KeyModifiers::NONE))),
+            ("ctrl+c", Some(key(KeyCode::Char('c'), KeyModifiers::CONTROL))),
+            ("Ctrl+Alt+x", Some(key(KeyCode::Char('x'), KeyModifiers::CONTROL | KeyModifiers::ALT))),
+            // 字符键的 Shift 体现在大小写上
+            ("shift+d", Some(key(KeyCode::Char('D'), KeyModifiers::NONE))),
+            ("D", Some(key(KeyCode::Char('D'), KeyModifiers::NONE))),
+            ("shift+up", Some(key(KeyCode::Up, KeyModifiers::SHIFT))),
+            ("+", Some(key(KeyCode::Char('+'), KeyModifiers::NONE))),
+            ("ctrl++", Some(key(KeyCode::Char('+'), KeyModifiers::CONTROL))),
+            ("space", Some(key(KeyCode::Char(' '), KeyModifiers::NONE))),
+            ("f5", Some(key(KeyCode::F(5), KeyModifiers::NONE))),
+            ("backtab", Some(key(KeyCode::BackTab, KeyModifiers::NONE))),
+            ("", None),
+            ("ab", None),
+            ("hyper+x", None),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(KeyBinding::parse(text), expected, "{}", text);
+        }
+    }
+
+    #[test]
+    fn events_match_bindings_regardless_of_shift_on_characters() {
+        let (keymap, warnings) = KeyMap::from_config(&BTreeMap::new());
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        let event = KeyEvent::new(KeyCode::Char('K'), KeyModifiers::SHIFT);
+        assert_eq!(keymap.action_for(&event), Some(Action::ToggleStochastic));
+        assert_eq!(keymap.describe(Action::RestoreDefaultPairs), None);
+        let event = KeyEvent::new(KeyCode::Up, KeyModifiers::SHIFT);
+        assert_eq!(keymap.action_for(&event), Some(Action::MovePairUp));
+    }
+
+    #[test]
+    fn overrides_replace_default_keys() {
+        let overrides = BTreeMap::from([("quit".to_string(), vec!["ctrl+c".to_string()])]);
+        let (keymap, warnings) = KeyMap::from_config(&overrides);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(keymap.action_for(&KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)), None);
+        assert_eq!(
+            keymap.action_for(&KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Action::Quit)
+        );
+        assert_eq!(keymap.describe(Action::Quit).as_deref(), Some("Ctrl+c"));
+    }
+
+    #[test]
+    fn reports_conflicts_and_invalid_entries() {
+        let overrides = BTreeMap::from([
+            // 与退出的默认按键冲突，用户配置优先
+            ("refresh".to_string(), vec!["q".to_string(), "bogus+x".to_string()]),
+            ("no_such_action".to_string(), vec!["x".to_string()]),
+        ]);
+        let (keymap, warnings) = KeyMap::from_config(&overrides);
+        assert_eq!(warnings.len(), 3, "{:?}", warnings);
+        assert!(warnings.iter().any(|w| w.contains("no_such_action")));
+        assert!(warnings.iter().any(|w| w.contains("bogus+x")));
+        assert!(warnings.iter().any(|w| w.contains("refresh") && w.contains("quit")));
+        assert_eq!(
+            keymap.action_for(&KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)),
+            Some(Action::Refresh)
+        );
+        assert_eq!(keymap.describe(Action::Quit), None);
+    }
+}
@@ -1,13 +1,21 @@
 mod app;
 mod api;
+mod chart;
+mod command;
 mod config;
 mod event;
+mod format;
+mod i18n;
+mod indicators;
+mod keymap;
+mod theme;
 mod ui;
 
 use crate::{
     app::App,
     config::AppConfig,
     event::{EventHandler, EventType, setup_terminal, restore_terminal},
+    i18n::trf,
     ui::draw,
 };
 use anyhow::Result;
@@ -21,6 +29,7 @@ use std::time::Duration;
 async fn main() -> Result<()> {
     // 加载配置
     let config = AppConfig::load().unwrap_or_default();
+    i18n::set_language(config.language);
     
     // 设置终端
     setup_terminal()?;
@@ -36,7 +45,7 @@ async fn main() -> Result<()> {
     app.refresh_data().await?;
 
     loop {
-        terminal.draw(|f| draw(f, &app))?;
+        terminal.draw(|f| draw(f, &mut app))?;
 
         if let Some(event) = event_handler.next_event().await? {
             match event {
@@ -44,7 +53,7 @@ async fn main() -> Result<()> {
                     match app.input_mode {
                         crate::app::InputMode::AddingPair => {
                             // 输入模式下的键盘处理
-                            match key {
+                            match key.code {
                                 KeyCode::Char(c) if c.is_alphanumeric() => {
                                     app.add_input_char(c);
                                 }
@@ -52,10 +61,12 @@ async fn main() -> Result<()> {
                                     app.remove_input_char();
                                 }
                                 KeyCode::Enter => {
-                                    if app.submit_input() {
-                                        println!("交易对添加成功并已保存!");
-                                    } else {
-                                        println!("添加失败: 可能已存在或超过最大数量");
+                                    // 添加成功后立即加载新交易对的数据
+                                    let added = app.submit_input();
+                                    if added {
+                                        if let Err(e) = app.refresh_data().await {
+                                            app.set_status(trf("刷新数据失败: {}", &[&e]));
+                                        }
                                     }
                                 }
                                 KeyCode::Esc => {
@@ -64,106 +75,79 @@ async fn main() -> Result<()> {
                                 _ => {}
                             }
                         }
-                        crate::app::InputMode::Normal => {
-                            // 正常模式下的键盘处理
-                            match key {
-                                KeyCode::Char('q') => {
-                                    app.quit();
-                                }
-                                KeyCode::Char('r') | KeyCode::Char(' ') => {
-                                    // 手动刷新
-                                    if let Err(e) = app.refresh_data().await {
-                                        eprintln!("刷新数据失败: {}", e);
-                                    }
-                                }
-                                KeyCode::Char('1') => {
-                                    if app.get_symbols().len() > 0 {
-                                        app.select_symbol(app.get_symbols()[0].clone());
-                                    }
-                                }
-                                KeyCode::Char('2') => {
-                                    if app.get_symbols().len() > 1 {
-                                        app.select_symbol(app.get_symbols()[1].clone());
-                                    }
-                                }
-                                KeyCode::Char('3') => {
-                                    if app.get_symbols().len() > 2 {
-                                        app.select_symbol(app.get_symbols()[2].clone());
-                                    }
-                                }
-                                KeyCode::Char('4') => {
-                                    if app.get_symbols().len() > 3 {
-                                        app.select_symbol(app.get_symbols()[3].clone());
-                                    }
+                        crate::app::InputMode::Command => {
+                            // 命令行模式下的键盘处理
+                            match key.code {
+                                KeyCode::Char(c) => {
+                                    app.add_input_char(c);
                                 }
-                                KeyCode::Char('5') => {
-                                    if app.get_symbols().len() > 4 {
-                                        app.select_symbol(app.get_symbols()[4].clone());
+                                KeyCode::Backspace => {
+                                    // 删空后再退格则退出命令行
+                                    if app.input_buffer.is_empty() {
+                                        app.exit_input_mode();
+                                    } else {
+                                        app.remove_input_char();
                                     }
                                 }
-                                KeyCode::Char('a') => {
-                                    // 进入添加交易对输入模式
-                                    app.enter_input_mode();
+                                KeyCode::Enter => {
+                                    app.submit_command().await;
                                 }
-                                KeyCode::Char('d') => {
-                                    // 删除自定义交易对
-                                    if let Some(selected) = app.selected_symbol.clone() {
-                                        if app.remove_custom_pair(&selected) {
-                                            println!("已删除交易对: {} 并已保存", selected);
-                                        }
-                                    }
-                                }
-                                KeyCode::Char('s') => {
-                                    // 手动保存配置
-                                    if let Err(e) = app.save_config() {
-                                        println!("保存配置失败: {}", e);
-                                    } else {
-                                        println!("配置已保存");
-                                    }
+                                KeyCode::Tab => {
+                                    app.complete_command();
                                 }
                                 KeyCode::Up => {
-                                    // 向上选择交易对
-                                    if let Some(current) = &app.selected_symbol {
-                                        let symbols = app.get_symbols();
-                                        if let Some(current_index) = symbols.iter().position(|s| s == current) {
-                                            if current_index > 0 {
-                                                app.select_symbol(symbols[current_index - 1].clone());
-                                            }
-                                        }
-                                    } else if !app.get_symbols().is_empty() {
-                                        app.select_symbol(app.get_symbols()[0].clone());
-                                    }
+                                    app.browse_history(-1);
                                 }
                                 KeyCode::Down => {
-                                    // 向下选择交易对
-                                    if let Some(current) = &app.selected_symbol {
-                                        let symbols = app.get_symbols();
-                                        if let Some(current_index) = symbols.iter().position(|s| s == current) {
-                                            if current_index < symbols.len() - 1 {
-                                                app.select_symbol(symbols[current_index + 1].clone());
-                                            }
-                                        }
-                                    } else if !app.get_symbols().is_empty() {
-                                        app.select_symbol(app.get_symbols()[0].clone());
-                                    }
+                                    app.browse_history(1);
+                                }
+                                KeyCode::Esc => {
+                                    app.exit_input_mode();
                                 }
                                 _ => {}
                             }
                         }
+                        crate::app::InputMode::Help => {
+                            // 帮助窗口中滚动或关闭
+                            match key.code {
+                                KeyCode::Up | KeyCode::Char('k') => app.scroll_help(-1),
+                                KeyCode::Down | KeyCode::Char('j') => app.scroll_help(1),
+                                KeyCode::PageUp => app.scroll_help(-10),
+                                KeyCode::PageDown | KeyCode::Char(' ') => app.scroll_help(10),
+                                KeyCode::Home => app.help_scroll = 0,
+                                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
+                                    app.exit_input_mode();
+                                }
+                                _ => {}
+                            }
+                        }
+                        crate::app::InputMode::Normal => {
+                            // 正常模式下按当前按键映射执行操作
+                            if let Some(action) = app.keymap.action_for(&key) {
+                                app.perform(action).await;
+                            }
+                        }
+                    }
+                }
+                EventType::Mouse(mouse) => {
+                    if let crate::app::InputMode::Normal = app.input_mode {
+                        app.handle_mouse(mouse);
+                        app.load_requested_history().await;
                     }
                 }
                 EventType::Tick => {
+                    app.expire_price_flashes();
                     // 检查是否需要刷新数据
                     if app.should_refresh() {
                         if let Err(e) = app.refresh_data().await {
-                            eprintln!("自动刷新数据失败: {}", e);
+                            app.set_status(trf("自动刷新数据失败: {}", &[&e]));
                         }
                     }
                 }
                 EventType::Refresh => {
                     // 手动刷新
                     if let Err(e) = app.refresh_data().await {
-                        eprintln!("刷新数据失败: {}", e);
+                        app.set_status(trf("刷新数据失败: {}", &[&e]));
                     }
                 }
             }
@@ -172,6 +156,10 @@ async fn main() -> Result<()> {
         if app.should_quit {
             break;
         }
+        // 切换交易对、平移或刷新后按需获取对比数据、订单簿和最近成交
+        app.load_comparison().await;
+        app.load_order_book().await;
+        app.load_recent_trades().await;
     }
 
     // 恢复终端
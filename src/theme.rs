@@ -0,0 +1,193 @@
+//! 界面配色。预设主题给出全部颜色，`[theme]` 中的单项颜色覆盖预设。
+
+use crate::config::{PriceColors, ThemeConfig, ThemePreset};
+use crate::i18n::trf;
+use ratatui::style::{Color, Style};
+use std::str::FromStr;
+
+/// 绘制界面时使用的颜色
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// 标题、表头、按键名等强调文字
+    pub primary: Color,
+    /// 选中行、获得焦点的边框、最新价等高亮
+    pub secondary: Color,
+    pub background: Color,
+    pub text: Color,
+    /// 坐标轴、刻度、分隔线等辅助元素
+    pub muted: Color,
+    pub up: Color,
+    pub down: Color,
+    /// 面积图的填充色
+    pub fill: Color,
+    /// 折线图、指标线、对比折线等数据序列依次使用的颜色
+    pub palette: [Color; 6],
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::preset(ThemePreset::Dark, PriceColors::GreenUp)
+    }
+}
+
+impl Theme {
+    pub fn preset(preset: ThemePreset, price_colors: PriceColors) -> Self {
+        let (up, down) = match price_colors {
+            PriceColors::GreenUp => (Color::Green, Color::Red),
+            PriceColors::RedUp => (Color::Red, Color::Green),
+            // Okabe-Ito 色盲友好配色中的天蓝和橙
+            PriceColors::BlueOrange => (Color::Rgb(0x56, 0xb4, 0xe9), Color::Rgb(0xe6, 0x9f, 0x00)),
+        };
+        let rgb = |hex: u32| Color::Rgb((hex >> 16) as u8, (hex >> 8) as u8, hex as u8);
+        match preset {
+            // 背景和文字沿用终端自身的颜色
+            ThemePreset::Dark => Self {
+                primary: Color::Cyan,
+                secondary: Color::Yellow,
+                background: Color::Reset,
+                text: Color::Reset,
+                muted: Color::DarkGray,
+                up,
+                down,
+                fill: Color::Blue,
+                palette: [
+                    Color::Yellow,
+                    Color::Cyan,
+                    Color::Magenta,
+                    Color::LightBlue,
+                    Color::LightGreen,
+                    Color::LightRed,
+                ],
+            },
+            ThemePreset::Light => Self {
+                primary: rgb(0x005f87),
+                secondary: rgb(0xaf5f00),
+                background: rgb(0xffffff),
+                text: rgb(0x1c1c1c),
+                muted: rgb(0x8a8a8a),
+                up,
+                down,
+                fill: rgb(0xafd7ff),
+                palette: [
+                    rgb(0xaf5f00),
+                    rgb(0x0087af),
+                    rgb(0xaf00af),
+                    rgb(0x005fd7),
+                    rgb(0x5f8700),
+                    rgb(0xd70000),
+                ],
+            },
+            ThemePreset::Solarized => Self {
+                primary: rgb(0x268bd2),
+                secondary: rgb(0xb58900),
+                background: rgb(0x002b36),
+                text: rgb(0x93a1a1),
+                muted: rgb(0x586e75),
+                up,
+                down,
+                fill: rgb(0x073642),
+                palette: [
+                    rgb(0xb58900),
+                    rgb(0x2aa198),
+                    rgb(0xd33682),
+                    rgb(0x268bd2),
+                    rgb(0x859900),
+                    rgb(0xcb4b16),
+                ],
+            },
+            ThemePreset::HighContrast => Self {
+                primary: rgb(0xffffff),
+                secondary: rgb(0xffff00),
+                background: rgb(0x000000),
+                text: rgb(0xffffff),
+                muted: rgb(0xc0c0c0),
+                up,
+                down,
+                fill: rgb(0x0000ff),
+                palette: [
+                    rgb(0xffff00),
+                    rgb(0x00ffff),
+                    rgb(0xff00ff),
+                    rgb(0xffffff),
+                    rgb(0x00ff00),
+                    rgb(0xff8000),
+                ],
+            },
+        }
+    }
+
+    /// 按配置生成主题，无效的颜色保留预设值并返回提示
+    pub fn from_config(config: &ThemeConfig) -> (Self, Vec<String>) {
+        let mut theme = Self::preset(config.preset, config.price_colors);
+        let mut warnings = Vec::new();
+        let overrides = [
+            ("primary", &config.primary, &mut theme.primary),
+            ("secondary", &config.secondary, &mut theme.secondary),
+            ("background", &config.background, &mut theme.background),
+            ("text", &config.text, &mut theme.text),
+            ("up", &config.up, &mut theme.up),
+            ("down", &config.down, &mut theme.down),
+        ];
+        for (name, value, color) in overrides {
+            let Some(value) = value else {
+                continue;
+            };
+            match parse_color(value) {
+                Ok(parsed) => *color = parsed,
+                Err(e) => warnings.push(format!("{}: {}", name, e)),
+            }
+        }
+        (theme, warnings)
+    }
+
+    /// 界面的默认样式：主题的文字颜色和背景色
+    pub fn base(&self) -> Style {
+        Style::default().fg(self.text).bg(self.background)
+    }
+
+    /// 上涨（含持平）和下跌的颜色
+    pub fn change(&self, rising: bool) -> Color {
+        if rising {
+            self.up
+        } else {
+            self.down
+        }
+    }
+}
+
+/// 解析 `#rrggbb` 或颜色名（如 `yellow`、`lightblue`）
+pub fn parse_color(text: &str) -> Result<Color, String> {
+    if let Some(hex) = text.strip_prefix('#') {
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(trf("无效的颜色 {}，应为 #rrggbb", &[&text]));
+        }
+        let value = u32::from_str_radix(hex, 16).map_err(|e| e.to_string())?;
+        return Ok(Color::Rgb((value >> 16) as u8, (value >> 8) as u8, value as u8));
+    }
+    Color::from_str(text).map_err(|_| trf("未知的颜色 {}", &[&text]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(parse_color("#00ff7f"), Ok(Color::Rgb(0, 255, 127)));
+        assert_eq!(parse_color("#ABCDEF"), Ok(Color::Rgb(0xab, 0xcd, 0xef)));
+    }
+
+    #[test]
+    fn parses_named_colors() {
+        assert_eq!(parse_color("yellow"), Ok(Color::Yellow));
+        assert_eq!(parse_color("lightblue"), Ok(Color::LightBlue));
+        assert_eq!(parse_color("dark-gray"), Ok(Color::DarkGray));
+    }
+
+    #[test]
+    fn rejects_invalid_colors() {
+        for text in ["#fff", "#0000000", "#", "#gg0000", "#12345z", "#+12345", "notacolor", ""] {
+            assert!(parse_color(text).is_err(), "{}", text);
+        }
+    }
+}
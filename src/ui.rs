@@ -1,64 +1,307 @@
 use crate::{
-    app::App,
-    api::KlineData,
+    app::{App, ChartView, LayoutAreas},
+    command::{self, COMMANDS},
+    api::{interval_label, KlineData, SymbolFilters, TickerPrice},
+    chart::{column_x, heikin_ashi, Candle, CandleChart, DepthChart, PriceLine, PriceScale},
+    config::{ChartConfig, ChartStyle, LayoutConfig, MovingAverageKind},
+    format::{self, format_change, format_number, format_percent, format_price, format_quantity},
+    i18n::{format_compact, format_timestamp, tr, trf, DateFormat},
+    indicators,
+    keymap::Action,
+    theme::{parse_color, Theme},
 };
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Paragraph, Table, Row},
+    text::{Line, Span},
+    widgets::{block::Title, Block, Borders, Cell, Clear, Paragraph, Table, Row, Wrap},
     Frame,
 };
 
-pub fn draw(f: &mut Frame, app: &App) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Min(0),
-            Constraint::Length(3),
-        ].as_ref())
-        .split(f.size());
+/// 能正常显示界面的最小终端尺寸，更小时只显示提示
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 12;
+/// 终端高度小于该值时，底部按键说明只占一行
+const COMPACT_HEIGHT: u16 = 30;
 
-    let main_area = chunks[0];
-    let footer_area = chunks[1];
+/// 主界面各区域的位置
+struct ScreenLayout {
+    table: Rect,
+    input: Rect,
+    chart: Rect,
+    footer: Rect,
+}
 
-    // 主内容区域
-    let main_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)].as_ref())
-        .split(main_area);
+/// 按终端大小划分区域：宽度足够时左右排列，列表宽度受比例和上限约束；
+/// 窄终端上列表、输入栏和图表改为上下排列
+fn screen_layout(area: Rect, config: &LayoutConfig) -> ScreenLayout {
+    let compact = area.height < COMPACT_HEIGHT;
+    let [main_area, footer] = split(
+        area,
+        Direction::Vertical,
+        [Constraint::Min(0), Constraint::Length(if compact { 1 } else { 3 })],
+    );
 
-    // 左侧区域分为交易对列表和输入区域
-    let left_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Min(0),
-            Constraint::Length(3),
-        ].as_ref())
-        .split(main_chunks[0]);
+    if area.width < config.stack_below {
+        let table_percent = config.stacked_table_percent.clamp(10, 90);
+        let [table, input, chart] = split(
+            main_area,
+            Direction::Vertical,
+            [
+                Constraint::Percentage(table_percent),
+                Constraint::Length(3),
+                Constraint::Min(0),
+            ],
+        );
+        return ScreenLayout { table, input, chart, footer };
+    }
+
+    let table_percent = config.table_percent.clamp(10, 90);
+    let table_width = (main_area.width as u32 * table_percent as u32 / 100) as u16;
+    let [left, chart] = split(
+        main_area,
+        Direction::Horizontal,
+        [Constraint::Length(table_width.min(config.table_max_width)), Constraint::Min(0)],
+    );
+    let [table, input] = split(left, Direction::Vertical, [Constraint::Min(0), Constraint::Length(3)]);
+    ScreenLayout { table, input, chart, footer }
+}
+
+fn split<const N: usize>(area: Rect, direction: Direction, constraints: [Constraint; N]) -> [Rect; N] {
+    let chunks = Layout::default().direction(direction).constraints(constraints).split(area);
+    std::array::from_fn(|i| chunks[i])
+}
+
+pub fn draw(f: &mut Frame, app: &mut App) {
+    // 整个界面先铺上主题的背景和文字颜色
+    f.render_widget(Block::default().style(app.theme.base()), f.size());
+
+    let size = f.size();
+    if size.width < MIN_WIDTH || size.height < MIN_HEIGHT {
+        // 不显示任何区域，鼠标操作也随之失效
+        app.layout = LayoutAreas::default();
+        let notice = trf(
+            "终端太小: {}×{}，至少需要 {}×{}",
+            &[&size.width, &size.height, &MIN_WIDTH, &MIN_HEIGHT],
+        );
+        let y = size.height.saturating_sub(2) / 2;
+        f.render_widget(
+            Paragraph::new(notice)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true })
+                .style(Style::default().fg(app.theme.secondary)),
+            Rect::new(0, y, size.width, size.height - y),
+        );
+        return;
+    }
+
+    let layout = screen_layout(size, &app.config.layout);
+
+    if let (true, Some(symbol)) = (app.detail_view, app.selected_symbol.clone()) {
+        // 详情页占据列表、输入栏和图表的全部区域，不响应列表和网格的鼠标操作
+        let main_area = layout.table.union(layout.input).union(layout.chart);
+        app.layout.symbol_table = Rect::default();
+        app.layout.grid_cells.clear();
+        draw_detail(f, app, &symbol, main_area);
+    } else {
+        draw_main(f, app, &layout);
+    }
 
-    draw_symbol_table(f, app, left_chunks[0]);
-    draw_input_area(f, app, left_chunks[1]);
-    draw_kline_chart(f, app, main_chunks[1]);
-    
     // Footer 显示按键说明
-    draw_footer(f, footer_area);
+    draw_footer(f, app, layout.footer);
+
+    if let crate::app::InputMode::Help = app.input_mode {
+        draw_help(f, app, size);
+    }
+}
+
+/// 交易对列表、输入栏和K线图
+fn draw_main(f: &mut Frame, app: &mut App, layout: &ScreenLayout) {
+    app.layout.symbol_table = layout.table;
+    let chart_area = kline_chunks(layout.chart)[1];
+    if app.config.grid.cells() > 1 {
+        // 网格模式下鼠标操作作用于获得焦点的格子
+        let cells = app.grid_symbols();
+        let areas: Vec<Rect> = grid_areas(chart_area, app.config.grid.cells())
+            .into_iter()
+            .take(cells.len())
+            .collect();
+        app.layout.chart = cells
+            .iter()
+            .position(|symbol| app.selected_symbol.as_ref() == Some(symbol))
+            .map_or(Rect::default(), |index| areas[index]);
+        app.layout.grid_cells = areas;
+    } else {
+        app.layout.chart = chart_area;
+        app.layout.grid_cells.clear();
+    }
+
+    draw_symbol_table(f, app, layout.table);
+    draw_input_area(f, app, layout.input);
+    let plot = draw_kline_chart(f, app, layout.chart).unwrap_or_default();
+    app.set_chart_plot(plot);
 }
 
-fn draw_symbol_table(f: &mut Frame, app: &App, area: Rect) {
+/// 帮助中的一组按键说明：(按键, 说明, 对应的操作名或命令)
+type HelpSection = (&'static str, Vec<(String, &'static str, String)>);
+
+/// 按使用场景分组的按键和命令说明，正常模式和图表的按键取自当前生效的按键映射
+fn help_sections(app: &App) -> Vec<HelpSection> {
+    let action_entries = |chart: bool| {
+        Action::ALL
+            .into_iter()
+            .filter(|action| action.is_chart() == chart)
+            .map(|action| {
+                let keys = app.keymap.describe(action).unwrap_or_else(|| tr("未绑定").to_string());
+                (keys, action.description(), format!(":{}", action.name()))
+            })
+            .collect::<Vec<_>>()
+    };
+    let fixed = |entries: &[(&str, &'static str)]| {
+        entries
+            .iter()
+            .map(|(keys, description)| (keys.to_string(), *description, String::new()))
+            .collect::<Vec<_>>()
+    };
+
+    let mut chart = action_entries(true);
+    chart.extend(fixed(&[
+        (tr("滚轮"), tr("在图表上缩放，在列表上移动选中项")),
+        (tr("拖动"), tr("平移查看历史K线")),
+        (tr("点击"), tr("选中交易对或切换网格焦点")),
+    ]));
+    let mut commands = fixed(&[
+        ("Enter", tr("执行")),
+        ("Esc", tr("取消")),
+        ("Tab", tr("补全，连续按下时切换候选")),
+        ("↑/↓", tr("浏览历史命令")),
+        ("Backspace", tr("删除字符，输入为空时退出")),
+    ]);
+    commands.extend(COMMANDS.iter().map(|name| {
+        let (args, description) = command::describe(name);
+        (format!(":{} {}", name, args).trim_end().to_string(), description, String::new())
+    }));
+    commands.push((tr(":<操作名>").to_string(), tr("执行同名操作，如 :refresh"), String::new()));
+
+    vec![
+        (tr("正常模式"), action_entries(false)),
+        (tr("图表"), chart),
+        (
+            tr("添加交易对"),
+            fixed(&[("Enter", tr("确认")), ("Esc", tr("取消")), ("Backspace", tr("删除字符"))]),
+        ),
+        (tr("命令行"), commands),
+        (
+            tr("帮助窗口"),
+            fixed(&[
+                ("↑/↓ j/k", tr("滚动")),
+                ("PgUp/PgDn", tr("翻页")),
+                ("Home", tr("回到顶部")),
+                ("Esc/q/?", tr("关闭")),
+            ]),
+        ),
+    ]
+}
+
+/// 居中的帮助窗口，内容超出高度时可滚动
+fn draw_help(f: &mut Frame, app: &mut App, area: Rect) {
+    let theme = app.theme;
+    let sections = help_sections(app);
+    // 按显示宽度对齐，中文占两列；过长的按键说明不参与对齐
+    let display_width = |text: &str| Span::raw(text).width();
+    let key_width = sections
+        .iter()
+        .flat_map(|(_, entries)| entries.iter().map(|(keys, _, _)| display_width(keys)))
+        .filter(|width| *width <= 24)
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = Vec::new();
+    for (title, entries) in sections {
+        if !lines.is_empty() {
+            lines.push(Line::default());
+        }
+        lines.push(Line::styled(title, Style::default().fg(theme.primary).add_modifier(Modifier::BOLD)));
+        for (keys, description, name) in entries {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {}{}  ", keys, " ".repeat(key_width.saturating_sub(display_width(&keys)))),
+                    Style::default().fg(theme.secondary),
+                ),
+                Span::raw(description),
+                Span::styled(format!("  {}", name), Style::default().fg(theme.muted)),
+            ]));
+        }
+    }
+
+    let width = area.width.saturating_sub(4).min(76);
+    let height = area.height.saturating_sub(2).min(lines.len() as u16 + 2);
+    let popup = Rect::new(area.x + (area.width - width) / 2, area.y + (area.height - height) / 2, width, height);
+    // 内容滚动到底后不再继续
+    let max_scroll = (lines.len() as u16).saturating_sub(height.saturating_sub(2));
+    app.help_scroll = app.help_scroll.min(max_scroll);
+
+    // 可以滚动时在标题中显示当前位置
+    let title = if max_scroll > 0 {
+        trf("帮助 ({}/{})", &[&(app.help_scroll + 1), &(max_scroll + 1)])
+    } else {
+        tr("帮助").to_string()
+    };
+    f.render_widget(Clear, popup);
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(titled_block(&theme, title).border_style(Style::default().fg(theme.secondary)))
+            .style(theme.base())
+            .scroll((app.help_scroll, 0)),
+        popup,
+    );
+}
+
+fn draw_symbol_table(f: &mut Frame, app: &mut App, area: Rect) {
+    let theme = app.theme;
     let symbols = app.get_symbols();
+    let selected_index = app
+        .selected_symbol
+        .as_ref()
+        .and_then(|selected| symbols.iter().position(|s| s == selected));
+    let columns = table_columns(area.width.saturating_sub(2));
+    let pick = |cells: Vec<Cell<'static>>| -> Vec<Cell<'static>> {
+        cells
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| columns.iter().any(|(column, _)| column == i))
+            .map(|(_, cell)| cell)
+            .collect()
+    };
     let mut rows = Vec::new();
 
     for symbol in symbols {
         if let Some(price) = app.ticker_prices.get(&symbol) {
             let price_change = price.price_change.parse::<f64>().unwrap_or(0.0);
             let price_change_percent = price.price_change_percent.parse::<f64>().unwrap_or(0.0);
-            let color = if price_change >= 0.0 { Color::Green } else { Color::Red };
-            
-            let row = Row::new(vec![
-                symbol.clone(),
-                format!("{:.2}%", price_change_percent),
-                price.price.clone(),
-            ]).style(Style::default().fg(color));
+            let color = theme.change(price_change >= 0.0);
+
+            // 走势图最后一个点使用最新价，使其随行情实时变化
+            let mut closes = app
+                .sparklines
+                .get(&symbol)
+                .map(|sparkline| sparkline.closes.clone())
+                .unwrap_or_default();
+            if let (Some(last), Ok(current)) = (closes.last_mut(), price.price.parse::<f64>()) {
+                *last = current;
+            }
+
+            let filters = app.symbol_filters.get(&symbol);
+            let row = Row::new(pick(vec![
+                Cell::from(symbol.clone()),
+                Cell::from(format_percent(price_change_percent)),
+                Cell::from(format_price(price.price.parse().unwrap_or(0.0), filters)).style(
+                    app.price_flash(&symbol)
+                        .map_or(Style::default(), |(rising, remaining)| flash_style(&theme, rising, remaining)),
+                ),
+                Cell::from(format_compact(price.volume.parse().unwrap_or(0.0))),
+                sparkline_cell(&closes, SPARKLINE_WIDTH, &theme),
+            ])).style(Style::default().fg(color));
             
             // 如果是选中的交易对，添加高亮
             if app.selected_symbol.as_ref() == Some(&symbol) {
@@ -67,189 +310,1621 @@ fn draw_symbol_table(f: &mut Frame, app: &App, area: Rect) {
                 rows.push(row);
             }
         } else {
-            let row = Row::new(vec![
-                symbol.clone(),
-                "0.00%".to_string(),
-                "加载中...".to_string(),
-            ]).style(Style::default().fg(Color::Yellow));
+            let row = Row::new(pick(vec![
+                Cell::from(symbol.clone()),
+                Cell::from(format_percent(0.0)),
+                Cell::from(tr("加载中...")),
+                Cell::from(""),
+                Cell::from(""),
+            ])).style(Style::default().fg(theme.secondary));
             rows.push(row);
         }
     }
 
-    let widths = [
-        Constraint::Percentage(40),
-        Constraint::Percentage(30),
-        Constraint::Percentage(30),
-    ];
+    let widths = columns.iter().map(|(_, width)| Constraint::Length(*width));
+    let header = pick(vec![
+        Cell::from(tr("交易对")),
+        Cell::from(tr("涨跌幅")),
+        Cell::from(tr("价格")),
+        Cell::from(tr("成交量")),
+        Cell::from(tr("24h走势")),
+    ]);
 
     let table = Table::new(rows, widths)
-        .header(Row::new(vec!["交易对", "涨跌幅", "价格"]))
-        .block(Block::default().borders(Borders::ALL).title("交易对列表"))
-        .style(Style::default().fg(Color::White));
+        .header(Row::new(header).style(Style::default().fg(theme.primary)))
+        .block(titled_block(&theme, tr("交易对列表")));
 
-    f.render_widget(table, area);
+    // 使用 TableState 让选中行始终处于可见范围
+    app.table_state.select(selected_index);
+    f.render_stateful_widget(table, area, &mut app.table_state);
+}
+
+/// 价格闪烁的样式：前半段以涨跌色作背景，后半段只保留加粗的涨跌色文字
+fn flash_style(theme: &Theme, rising: bool, remaining: f64) -> Style {
+    let color = theme.change(rising);
+    if remaining > 0.5 {
+        let text = match theme.background {
+            Color::Reset => Color::Black,
+            background => background,
+        };
+        Style::default().fg(text).bg(color)
+    } else {
+        Style::default().fg(color).add_modifier(Modifier::BOLD)
+    }
+}
+
+/// 交易对列表中走势图的字符宽度
+const SPARKLINE_WIDTH: usize = 12;
+
+/// 交易对列表各列（交易对、涨跌幅、价格、成交量、走势图）完整显示所需的最小宽度
+const TABLE_MIN_WIDTHS: [u16; 5] = [8, 8, 9, 10, SPARKLINE_WIDTH as u16];
+
+/// 剩余宽度按比例分给文字列，走势图宽度固定
+const TABLE_WEIGHTS: [u16; 5] = [30, 20, 28, 22, 0];
+
+/// 列表变窄时依次隐藏成交量、走势图和涨跌幅，交易对和价格始终保留
+const TABLE_PRIORITY: [usize; 5] = [0, 2, 1, 4, 3];
+
+/// 在 `width` 宽度内能完整显示的列及其宽度，按原有顺序返回
+fn table_columns(width: u16) -> Vec<(usize, u16)> {
+    let mut used = 0;
+    let mut columns = Vec::new();
+    for (n, column) in TABLE_PRIORITY.into_iter().enumerate() {
+        // 列之间有一格间隔
+        let needed = TABLE_MIN_WIDTHS[column] + u16::from(n > 0);
+        if n >= 2 && used + needed > width {
+            break;
+        }
+        used += needed;
+        columns.push(column);
+    }
+    columns.sort_unstable();
+
+    let extra = width.saturating_sub(used) as u32;
+    let total_weight: u32 = columns.iter().map(|i| TABLE_WEIGHTS[*i] as u32).sum();
+    columns
+        .into_iter()
+        .map(|i| {
+            let share = (extra * TABLE_WEIGHTS[i] as u32).checked_div(total_weight).unwrap_or(0);
+            (i, TABLE_MIN_WIDTHS[i] + share as u16)
+        })
+        .collect()
+}
+
+/// 用 `▁`~`█` 绘制的迷你走势图，数据点多于宽度时按位置抽样，颜色取决于首尾涨跌
+fn sparkline_cell(values: &[f64], width: usize, theme: &Theme) -> Cell<'static> {
+    const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    if values.is_empty() || width == 0 {
+        return Cell::from("");
+    }
+    let samples: Vec<f64> = if values.len() > width {
+        (0..width)
+            .map(|i| values[i * (values.len() - 1) / (width - 1).max(1)])
+            .collect()
+    } else {
+        values.to_vec()
+    };
+    let (min, max) = samples
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(min, max), v| (min.min(*v), max.max(*v)));
+    let text: String = samples
+        .iter()
+        .map(|value| {
+            // 没有波动时画在中间高度
+            let level = if max > min { (value - min) / (max - min) * 7.0 } else { 3.0 };
+            LEVELS[level.round() as usize]
+        })
+        .collect();
+    let rising = values[values.len() - 1] >= values[0];
+    Cell::from(text).style(Style::default().fg(theme.change(rising)))
 }
 
 fn draw_input_area(f: &mut Frame, app: &App, area: Rect) {
     let input_text = match app.input_mode {
         crate::app::InputMode::AddingPair => {
-            format!("添加交易对: {}_", app.input_buffer)
+            trf("添加交易对: {}_", &[&app.input_buffer])
         }
-        crate::app::InputMode::Normal => {
-            "按 'A' 添加交易对".to_string()
+        crate::app::InputMode::Command => {
+            format!(":{}_", app.input_buffer)
         }
+        crate::app::InputMode::Normal | crate::app::InputMode::Help => match &app.status_message {
+            Some(message) => message.clone(),
+            None => tr("按 'A' 添加交易对").to_string(),
+        },
     };
 
     let style = match app.input_mode {
-        crate::app::InputMode::AddingPair => Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
-        crate::app::InputMode::Normal => Style::default().fg(Color::Gray),
+        crate::app::InputMode::AddingPair | crate::app::InputMode::Command => {
+            Style::default().fg(app.theme.secondary).add_modifier(Modifier::BOLD)
+        }
+        crate::app::InputMode::Normal | crate::app::InputMode::Help => Style::default().fg(app.theme.muted),
+    };
+    let title = match app.input_mode {
+        crate::app::InputMode::Command => tr("命令"),
+        _ => tr("交易对输入"),
     };
 
     let paragraph = Paragraph::new(input_text)
-        .block(Block::default().borders(Borders::ALL).title("交易对输入"))
+        .block(titled_block(&app.theme, title))
         .style(style);
 
     f.render_widget(paragraph, area);
 }
 
-fn draw_footer(f: &mut Frame, area: Rect) {
-    let footer_text = "Q:退出  R:刷新  1-5:快速选择  ↑↓:导航  A:添加交易对  D:删除交易对  S:保存配置  Enter:确认";
-    
+fn draw_footer(f: &mut Frame, app: &App, area: Rect) {
+    // 输入模式的按键固定，正常模式根据当前生效的按键映射生成说明
+    let footer_text = match app.input_mode {
+        crate::app::InputMode::AddingPair => tr("Enter:确认  Esc:取消  Backspace:删除字符").to_string(),
+        crate::app::InputMode::Command => {
+            trf("Enter:执行  Esc:取消  Tab:补全  ↑↓:历史  命令: {}", &[&COMMANDS.join(" ")])
+        }
+        crate::app::InputMode::Help => tr("↑↓/jk:滚动  PgUp/PgDn:翻页  Home:顶部  Esc/q/?:关闭").to_string(),
+        crate::app::InputMode::Normal => normal_footer_text(app),
+    };
+
+    // 紧凑模式下只有一行，省去分隔线
+    let borders = if area.height > 1 { Borders::TOP } else { Borders::NONE };
     let paragraph = Paragraph::new(footer_text)
-        .block(Block::default().borders(Borders::TOP))
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
-    
+        .block(Block::default().borders(borders).border_style(Style::default().fg(app.theme.muted)))
+        .style(Style::default().fg(app.theme.primary).add_modifier(Modifier::BOLD));
+
     f.render_widget(paragraph, area);
 }
 
-fn draw_kline_chart(f: &mut Frame, app: &App, area: Rect) {
+fn normal_footer_text(app: &App) -> String {
+    // 打开和关闭详情只显示当前可用的一个
+    let hidden = if app.detail_view { Action::OpenDetail } else { Action::CloseDetail };
+    Action::ALL
+        .into_iter()
+        .filter(|action| *action != hidden && !(app.detail_view && action.edits_pairs()))
+        .filter_map(|action| match action {
+            Action::Select1 => quick_select_hint(app),
+            _ if !action.in_footer() => None,
+            _ => app
+                .keymap
+                .describe(action)
+                .map(|keys| format!("{}:{}", keys, action.description())),
+        })
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// 快速选择的按键在底部栏合并为一项，全部绑定时显示为 `1-5:快速选择`
+fn quick_select_hint(app: &App) -> Option<String> {
+    let actions: Vec<Action> = Action::ALL
+        .into_iter()
+        .filter(|action| action.select_index().is_some())
+        .collect();
+    let keys: Vec<String> = actions.iter().filter_map(|action| app.keymap.describe(*action)).collect();
+    let keys = match keys.as_slice() {
+        [] => return None,
+        [first, .., last] if keys.len() == actions.len() => format!("{}-{}", first, last),
+        _ => keys.join("/"),
+    };
+    Some(format!("{}:{}", keys, tr("快速选择")))
+}
+
+/// 带边框的区块，标题使用主题的强调色
+fn titled_block<'a>(theme: &Theme, title: impl Into<Title<'a>>) -> Block<'a> {
+    Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_style(Style::default().fg(theme.primary))
+}
+
+/// 分割区域：上方信息栏，下方K线图
+fn kline_chunks(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // 信息栏
+            Constraint::Min(0),     // K线图
+        ].as_ref())
+        .split(area)
+}
+
+/// 返回K线实际绘制的区域（不含边框和坐标轴），用于鼠标位置到K线的换算
+fn draw_kline_chart(f: &mut Frame, app: &App, area: Rect) -> Option<Rect> {
     if let Some(selected_symbol) = &app.selected_symbol {
-        // 分割区域：上方信息栏，下方K线图
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),  // 信息栏
-                Constraint::Min(0),     // K线图
-            ].as_ref())
-            .split(area);
+        let chunks = kline_chunks(area);
 
         let info_area = chunks[0];
         let chart_area = chunks[1];
 
-        // 绘制信息栏
-        draw_symbol_info(f, app, selected_symbol, info_area);
+        let klines = app.kline_data.get(selected_symbol);
+        let crosshair = klines.and_then(|klines| app.crosshair_index(klines.len()));
+
+        // 绘制信息栏，光标模式下显示光标所在K线的数据
+        match (klines, crosshair) {
+            (Some(klines), Some(index)) => draw_candle_info(f, &app.theme, app.symbol_filters.get(selected_symbol), &klines[index], info_area),
+            _ => draw_symbol_info(f, app, selected_symbol, info_area),
+        }
+
+        if app.config.depth.show {
+            draw_depth_chart(f, app, selected_symbol, chart_area);
+            return None;
+        }
 
-        // 绘制K线图
-        if let Some(klines) = app.kline_data.get(selected_symbol) {
-            draw_candlestick_chart(f, klines, selected_symbol, chart_area);
+        // 绘制K线图，网格模式下依次绘制各格子，选中交易对所在的格子获得焦点
+        let cells = app.grid_symbols();
+        if app.config.grid.cells() > 1 {
+            let mut focused_plot = None;
+            for (symbol, cell) in cells.iter().zip(grid_areas(chart_area, app.config.grid.cells())) {
+                if symbol == selected_symbol {
+                    focused_plot = draw_chart_cell(f, app, symbol, crosshair, app.chart_view, true, cell);
+                } else {
+                    // 其他格子显示最新的一屏K线
+                    let view = ChartView {
+                        visible: Some(cell.width.saturating_sub(2) as usize),
+                        ..ChartView::default()
+                    };
+                    draw_chart_cell(f, app, symbol, None, view, false, cell);
+                }
+            }
+            focused_plot
         } else {
-            let paragraph = Paragraph::new("加载K线数据中...")
-                .block(Block::default().borders(Borders::ALL).title(format!("K线图 - {}", selected_symbol)));
-            f.render_widget(paragraph, chart_area);
+            draw_chart_cell(f, app, selected_symbol, crosshair, app.chart_view, false, chart_area)
         }
     } else {
-        let paragraph = Paragraph::new("请选择一个交易对查看K线图")
-            .block(Block::default().borders(Borders::ALL).title("K线图"));
+        let paragraph = Paragraph::new(tr("请选择一个交易对查看K线图")).block(titled_block(&app.theme, tr("K线图")));
         f.render_widget(paragraph, area);
+        None
+    }
+}
+
+/// 深度图：中间价上下一定范围内的累计买卖盘，首行显示买一、卖一和价差，
+/// 右侧为累计挂单量刻度，底行为价格刻度
+fn draw_depth_chart(f: &mut Frame, app: &App, symbol: &str, area: Rect) {
+    let theme = &app.theme;
+    let range = app.config.depth.range_percent;
+    let block = titled_block(theme, trf("深度图 - {} (±{}%)", &[&symbol, &range]));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let loaded = app
+        .order_books
+        .get(symbol)
+        .and_then(|book| Some((book, book.mid_price()?)));
+    let Some((book, mid)) = loaded else {
+        f.render_widget(Paragraph::new(tr("加载中...")), inner);
+        return;
+    };
+    // 至少需要图例、价格轴和两行图表
+    if inner.height < 4 {
+        return;
+    }
+
+    let (bid, bid_quantity) = book.bids[0];
+    let (ask, ask_quantity) = book.asks[0];
+    let spread = ask - bid;
+    let filters = app.symbol_filters.get(symbol);
+    // 价差和价格刻度按交易对的价格精度显示，未获取到精度时按买一、卖一本身的位数
+    let decimals = filters.map_or(price_decimals(bid, ask), |filters| format::decimals(filters.tick_size));
+    let legend = Line::from(vec![
+        Span::styled(
+            trf("买一: {} ({})  ", &[&format_price(bid, filters), &format_quantity(bid_quantity, filters)]),
+            Style::default().fg(theme.up),
+        ),
+        Span::styled(
+            trf("卖一: {} ({})  ", &[&format_price(ask, filters), &format_quantity(ask_quantity, filters)]),
+            Style::default().fg(theme.down),
+        ),
+        Span::styled(
+            // 价差由浮点数相减得到，没有交易所精度时按买一卖一的位数取整
+            trf("价差: {}", &[&format_number(spread, Some(decimals))]),
+            Style::default().fg(theme.secondary),
+        ),
+    ]);
+    f.render_widget(Paragraph::new(legend), Rect { height: 1, ..inner });
+
+    let (min_price, max_price) = (mid * (1.0 - range / 100.0), mid * (1.0 + range / 100.0));
+    let chart = DepthChart::new(book, min_price, max_price)
+        .bid_style(Style::default().fg(theme.up))
+        .ask_style(Style::default().fg(theme.down))
+        .mid_style(Style::default().fg(theme.secondary));
+    let depth_scale = PriceScale::new(0.0, chart.max_depth(), false);
+    let (depth_ticks, depth_decimals) = price_ticks(depth_scale, (inner.height as usize - 2) / 2);
+    let axis_width = depth_ticks
+        .iter()
+        .map(|tick| format!("─{:.*}", depth_decimals, tick).chars().count() as u16)
+        .max()
+        .unwrap_or(0)
+        .min(inner.width / 3);
+
+    let plot = Rect {
+        y: inner.y + 1,
+        height: inner.height - 2,
+        width: inner.width - axis_width,
+        ..inner
+    };
+    let axis = Rect {
+        x: plot.x + plot.width,
+        width: axis_width,
+        ..plot
+    };
+    let guide_style = Style::default().fg(theme.muted);
+    for tick in depth_ticks {
+        let y = axis.y + depth_scale.row(tick, axis.height);
+        let text = format!("─{:.*}", depth_decimals, tick);
+        f.render_widget(Paragraph::new(text).style(guide_style), Rect { y, height: 1, ..axis });
+    }
+
+    // 价格轴：两端为范围边界，中间价标在其所在列
+    let price_row = Rect {
+        y: plot.y + plot.height,
+        height: 1,
+        ..plot
+    };
+    let mid_label = format!("┴{:.*}", decimals, mid);
+    let mid_x = chart.column(mid, plot.width);
+    let max_label = format!("{:.*}┴", decimals, max_price);
+    let labels = [
+        (0, format!("┴{:.*}", decimals, min_price), guide_style),
+        (
+            mid_x.min(plot.width.saturating_sub(mid_label.chars().count() as u16)),
+            mid_label,
+            Style::default().fg(theme.secondary),
+        ),
+        (plot.width.saturating_sub(max_label.chars().count() as u16), max_label, guide_style),
+    ];
+    for (x, label, style) in labels {
+        let width = (label.chars().count() as u16).min(price_row.width.saturating_sub(x));
+        f.render_widget(
+            Paragraph::new(label).style(style),
+            Rect { x: price_row.x + x, width, ..price_row },
+        );
+    }
+
+    f.render_widget(chart, plot);
+}
+
+/// 深度图价格的小数位数：按买一、卖一中小数位较多的一个
+fn price_decimals(bid: f64, ask: f64) -> usize {
+    [bid, ask]
+        .iter()
+        .map(|price| price.to_string().split_once('.').map_or(0, |(_, fraction)| fraction.len()))
+        .max()
+        .unwrap_or(0)
+}
+
+/// 网格的各格子区域：2 个并排，4 个为 2×2，6 个为 2 行 3 列
+fn grid_areas(area: Rect, size: usize) -> Vec<Rect> {
+    let (rows, columns) = match size {
+        2 => (1, 2),
+        4 => (2, 2),
+        6 => (2, 3),
+        _ => (1, 1),
+    };
+    let row_areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Ratio(1, rows); rows as usize])
+        .split(area);
+    row_areas
+        .iter()
+        .flat_map(|row| {
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Ratio(1, columns); columns as usize])
+                .split(*row)
+                .to_vec()
+        })
+        .collect()
+}
+
+/// 绘制一个交易对的图表及其边框，K线尚未加载时显示提示
+fn draw_chart_cell(
+    f: &mut Frame,
+    app: &App,
+    symbol: &str,
+    crosshair: Option<usize>,
+    view: ChartView,
+    focused: bool,
+    area: Rect,
+) -> Option<Rect> {
+    let border_style = if focused {
+        Style::default().fg(app.theme.secondary)
+    } else {
+        Style::default()
+    };
+    let title = trf(
+        "K线图 - {} ({}周期 · {})",
+        &[
+            &symbol,
+            &interval_label(app.config.interval_for(symbol)),
+            &app.config.chart_style_for(symbol).label(),
+        ],
+    );
+    let block = titled_block(&app.theme, title).border_style(border_style);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    match app.kline_data.get(symbol) {
+        Some(klines) => draw_chart_panes(f, app, symbol, klines, crosshair, view, inner),
+        None => {
+            f.render_widget(Paragraph::new(tr("加载K线数据中...")), inner);
+            None
+        }
     }
 }
 
 fn draw_symbol_info(f: &mut Frame, app: &App, symbol: &str, area: Rect) {
     if let Some(price) = app.ticker_prices.get(symbol) {
         let price_change = price.price_change.parse::<f64>().unwrap_or(0.0);
-        let color = if price_change >= 0.0 { Color::Green } else { Color::Red };
+        let color = app.theme.change(price_change >= 0.0);
         
-        let info_text = format!(
-            "{} | 价格: {} | 涨跌: {} ({:.2}%) | 24h高: {} | 24h低: {}",
-            symbol,
-            price.price,
-            price.price_change,
-            price.price_change_percent.parse::<f64>().unwrap_or(0.0),
-            price.high_24h,
-            price.low_24h
+        let filters = app.symbol_filters.get(symbol);
+        let parse = |text: &str| text.parse::<f64>().unwrap_or(0.0);
+        let info_text = trf(
+            "{} | 价格: {} | 涨跌: {} ({}) | 24h高: {} | 24h低: {} | 24h量: {}",
+            &[
+                &symbol,
+                &format_price(parse(&price.price), filters),
+                &format_change(price_change, filters),
+                &format_percent(parse(&price.price_change_percent)),
+                &format_price(parse(&price.high_24h), filters),
+                &format_price(parse(&price.low_24h), filters),
+                &format_compact(parse(&price.volume)),
+            ],
         );
 
         let paragraph = Paragraph::new(info_text)
-            .block(Block::default().borders(Borders::ALL).title("交易对信息"))
+            .block(titled_block(&app.theme, tr("交易对信息")))
             .style(Style::default().fg(color));
 
         f.render_widget(paragraph, area);
     } else {
-        let paragraph = Paragraph::new(format!("{} | 加载价格信息中...", symbol))
-            .block(Block::default().borders(Borders::ALL).title("交易对信息"))
-            .style(Style::default().fg(Color::Yellow));
+        let paragraph = Paragraph::new(trf("{} | 加载价格信息中...", &[&symbol]))
+            .block(titled_block(&app.theme, tr("交易对信息")))
+            .style(Style::default().fg(app.theme.secondary));
 
         f.render_widget(paragraph, area);
     }
 }
 
-fn draw_candlestick_chart(f: &mut Frame, klines: &[KlineData], symbol: &str, area: Rect) {
-    if klines.is_empty() {
-        let paragraph = Paragraph::new("暂无K线数据")
-            .block(Block::default().borders(Borders::ALL).title(format!("K线图 - {}", symbol)));
-        f.render_widget(paragraph, area);
+/// 全屏详情页：上方为24小时行情和交易规则，下方为K线图、深度图和最近成交，
+/// 底部保留输入栏以显示状态和命令。窄终端上各部分改为上下排列。
+fn draw_detail(f: &mut Frame, app: &mut App, symbol: &str, area: Rect) {
+    let [content, input] = split(area, Direction::Vertical, [Constraint::Min(0), Constraint::Length(3)]);
+    draw_input_area(f, app, input);
+
+    let stats = detail_stats(app, symbol);
+    let rules = detail_rules(app, symbol);
+    let wide = area.width >= app.config.layout.stack_below;
+    let (stats_columns, rules_columns) = if wide { (3, 2) } else { (2, 2) };
+    let stats_height = field_rows(stats.len(), stats_columns) + 2;
+    let rules_height = field_rows(rules.len(), rules_columns) + 2;
+
+    let (stats_area, rules_area, chart_area, depth_area, trades_area) = if wide {
+        let [top, bottom] = split(
+            content,
+            Direction::Vertical,
+            [Constraint::Length(stats_height.max(rules_height)), Constraint::Min(0)],
+        );
+        let [stats_area, rules_area] = split(
+            top,
+            Direction::Horizontal,
+            [Constraint::Percentage(60), Constraint::Percentage(40)],
+        );
+        let [chart_area, side] = split(
+            bottom,
+            Direction::Horizontal,
+            [Constraint::Percentage(65), Constraint::Percentage(35)],
+        );
+        let [depth_area, trades_area] = split(
+            side,
+            Direction::Vertical,
+            [Constraint::Percentage(50), Constraint::Percentage(50)],
+        );
+        (stats_area, rules_area, chart_area, depth_area, trades_area)
+    } else {
+        let [stats_area, rules_area, chart_area, bottom] = split(
+            content,
+            Direction::Vertical,
+            [
+                Constraint::Length(stats_height),
+                Constraint::Length(rules_height),
+                Constraint::Min(8),
+                Constraint::Percentage(30),
+            ],
+        );
+        let [depth_area, trades_area] = split(
+            bottom,
+            Direction::Horizontal,
+            [Constraint::Percentage(50), Constraint::Percentage(50)],
+        );
+        (stats_area, rules_area, chart_area, depth_area, trades_area)
+    };
+
+    draw_fields(f, &app.theme, tr("24小时行情"), &stats, stats_columns, stats_area);
+    draw_fields(f, &app.theme, tr("交易规则"), &rules, rules_columns, rules_area);
+    draw_depth_chart(f, app, symbol, depth_area);
+    draw_recent_trades(f, app, symbol, trades_area);
+
+    // 光标模式下在K线图上方显示光标所在K线的数据
+    let klines = app.kline_data.get(symbol);
+    let crosshair = klines.and_then(|klines| app.crosshair_index(klines.len()));
+    let chart_area = match (klines, crosshair) {
+        (Some(klines), Some(index)) => {
+            let chunks = kline_chunks(chart_area);
+            draw_candle_info(f, &app.theme, app.symbol_filters.get(symbol), &klines[index], chunks[0]);
+            chunks[1]
+        }
+        _ => chart_area,
+    };
+    app.layout.chart = chart_area;
+    let plot = draw_chart_cell(f, app, symbol, crosshair, app.chart_view, false, chart_area).unwrap_or_default();
+    app.set_chart_plot(plot);
+}
+
+/// 详情页中的一项：(名称, 值, 值的颜色)
+type DetailField = (&'static str, String, Option<Color>);
+
+/// 24小时行情的各项数据，价格尚未加载时值为 `-`
+fn detail_stats(app: &App, symbol: &str) -> Vec<DetailField> {
+    let filters = app.symbol_filters.get(symbol);
+    let ticker = app.ticker_prices.get(symbol);
+    let value = |field: fn(&TickerPrice) -> &str| {
+        ticker.and_then(|ticker| field(ticker).parse::<f64>().ok())
+    };
+    let price = |field: fn(&TickerPrice) -> &str| {
+        value(field).map_or_else(|| "-".to_string(), |v| format_price(v, filters))
+    };
+    let quote = |price: fn(&TickerPrice) -> &str, quantity: fn(&TickerPrice) -> &str| {
+        match (value(price), value(quantity)) {
+            (Some(p), Some(q)) => format!("{} ({})", format_price(p, filters), format_quantity(q, filters)),
+            _ => "-".to_string(),
+        }
+    };
+
+    let change = value(|t| &t.price_change).unwrap_or(0.0);
+    let change_color = Some(app.theme.change(change >= 0.0));
+    let change_text = match (ticker, value(|t| &t.price_change_percent)) {
+        (Some(_), Some(percent)) => format!("{} ({})", format_change(change, filters), format_percent(percent)),
+        _ => "-".to_string(),
+    };
+    let spread = match (value(|t| &t.bid_price), value(|t| &t.ask_price)) {
+        (Some(bid), Some(ask)) if bid > 0.0 && ask > 0.0 => {
+            let decimals = filters.map_or(price_decimals(bid, ask), |filters| format::decimals(filters.tick_size));
+            format_number(ask - bid, Some(decimals))
+        }
+        _ => "-".to_string(),
+    };
+    let compact = |field: fn(&TickerPrice) -> &str| value(field).map_or_else(|| "-".to_string(), format_compact);
+
+    vec![
+        (tr("最新价"), price(|t| &t.price), change_color),
+        (tr("涨跌"), change_text, change_color),
+        (tr("开盘价"), price(|t| &t.open_24h), None),
+        (tr("最高价"), price(|t| &t.high_24h), None),
+        (tr("最低价"), price(|t| &t.low_24h), None),
+        (tr("加权均价"), price(|t| &t.weighted_avg_price), None),
+        (tr("成交量"), compact(|t| &t.volume), None),
+        (tr("成交额"), compact(|t| &t.quote_volume), None),
+        (
+            tr("成交笔数"),
+            ticker.map_or_else(|| "-".to_string(), |t| format_number(t.trade_count as f64, Some(0))),
+            None,
+        ),
+        (tr("买一"), quote(|t| &t.bid_price, |t| &t.bid_quantity), Some(app.theme.up)),
+        (tr("卖一"), quote(|t| &t.ask_price, |t| &t.ask_quantity), Some(app.theme.down)),
+        (tr("价差"), spread, None),
+    ]
+}
+
+/// `exchangeInfo` 中的交易规则，尚未获取时值为 `-`
+fn detail_rules(app: &App, symbol: &str) -> Vec<DetailField> {
+    let filters = app.symbol_filters.get(symbol);
+    let field = |text: fn(&SymbolFilters) -> String| filters.map_or_else(|| "-".to_string(), text);
+    // 上限为 0 表示不限制
+    fn range(min: f64, max: f64, decimals: usize) -> String {
+        let max = if max > 0.0 { format_number(max, Some(decimals)) } else { "∞".to_string() };
+        format!("{} ~ {}", format_number(min, Some(decimals)), max)
+    }
+
+    vec![
+        (tr("状态"), field(|f| f.status.clone()), None),
+        (tr("资产"), field(|f| format!("{} / {}", f.base_asset, f.quote_asset)), None),
+        (tr("价格步长"), field(|f| format_number(f.tick_size, None)), None),
+        (tr("数量步长"), field(|f| format_number(f.step_size, None)), None),
+        (
+            tr("价格范围"),
+            field(|f| range(f.min_price, f.max_price, format::decimals(f.tick_size))),
+            None,
+        ),
+        (
+            tr("数量范围"),
+            field(|f| range(f.min_quantity, f.max_quantity, format::decimals(f.step_size))),
+            None,
+        ),
+        (tr("最小成交额"), field(|f| format_number(f.min_notional, None)), None),
+    ]
+}
+
+/// `count` 项按每行 `columns` 项排列所需的行数
+fn field_rows(count: usize, columns: usize) -> u16 {
+    count.div_ceil(columns.max(1)) as u16
+}
+
+/// 按行排列的名称和值，每行 `columns` 项，名称按显示宽度对齐
+fn draw_fields(f: &mut Frame, theme: &Theme, title: &str, fields: &[DetailField], columns: usize, area: Rect) {
+    let label_width = fields
+        .iter()
+        .map(|(label, _, _)| Span::raw(*label).width() as u16)
+        .max()
+        .unwrap_or(0);
+    let rows: Vec<Row> = fields
+        .chunks(columns)
+        .map(|chunk| {
+            Row::new(chunk.iter().flat_map(|(label, value, color)| {
+                let value_style = color.map_or(Style::default(), |color| Style::default().fg(color));
+                [
+                    Cell::from(*label).style(Style::default().fg(theme.muted)),
+                    Cell::from(value.clone()).style(value_style),
+                ]
+            }))
+        })
+        .collect();
+    let widths = (0..columns).flat_map(|_| [Constraint::Length(label_width), Constraint::Fill(1)]);
+
+    let table = Table::new(rows, widths).block(titled_block(theme, title.to_string()));
+    f.render_widget(table, area);
+}
+
+/// 最近成交，最新的在最上方。主动买入用上涨色，主动卖出用下跌色
+fn draw_recent_trades(f: &mut Frame, app: &App, symbol: &str, area: Rect) {
+    let theme = &app.theme;
+    let block = titled_block(theme, tr("最近成交"));
+    let Some(trades) = app.recent_trades.get(symbol) else {
+        f.render_widget(Paragraph::new(tr("加载中...")).block(block), area);
         return;
+    };
+
+    let filters = app.symbol_filters.get(symbol);
+    let rows: Vec<Row> = trades
+        .iter()
+        .rev()
+        .map(|trade| {
+            Row::new(vec![
+                Cell::from(format_timestamp(trade.time, DateFormat::TimeSeconds))
+                    .style(Style::default().fg(theme.muted)),
+                Cell::from(format_price(trade.price, filters))
+                    .style(Style::default().fg(theme.change(!trade.is_buyer_maker))),
+                Cell::from(format_quantity(trade.quantity, filters)),
+            ])
+        })
+        .collect();
+    let header = Row::new(vec![
+        Cell::from(tr("时间")),
+        Cell::from(tr("价格")),
+        Cell::from(tr("数量")),
+    ])
+    .style(Style::default().fg(theme.primary));
+    let widths = [Constraint::Length(8), Constraint::Fill(1), Constraint::Fill(1)];
+
+    f.render_widget(Table::new(rows, widths).header(header).block(block), area);
+}
+
+/// 光标所在K线的开盘时间和 OHLCV
+fn draw_candle_info(f: &mut Frame, theme: &Theme, filters: Option<&SymbolFilters>, kline: &KlineData, area: Rect) {
+    let parse = |text: &str| text.parse::<f64>().unwrap_or(0.0);
+    let open = parse(&kline.open);
+    let close = parse(&kline.close);
+    let change_percent = if open != 0.0 { (close - open) / open * 100.0 } else { 0.0 };
+    let color = theme.change(close >= open);
+    let time = format_timestamp(kline.open_time, DateFormat::DateTime);
+    let info_text = trf(
+        "{} | 开: {} | 高: {} | 低: {} | 收: {} | 量: {} | 涨跌: {}",
+        &[
+            &time,
+            &format_price(open, filters),
+            &format_price(parse(&kline.high), filters),
+            &format_price(parse(&kline.low), filters),
+            &format_price(close, filters),
+            &format_compact(parse(&kline.volume)),
+            &format_percent(change_percent),
+        ],
+    );
+
+    let paragraph = Paragraph::new(info_text)
+        .block(titled_block(theme, tr("K线数据")))
+        .style(Style::default().fg(color));
+
+    f.render_widget(paragraph, area);
+}
+
+/// K线图与下方的副图共用一个边框，并且按同样的方式把K线映射到列上。
+/// 右侧为价格轴，底部为时间轴。`inner` 为边框内的区域。
+fn draw_chart_panes(
+    f: &mut Frame,
+    app: &App,
+    symbol: &str,
+    klines: &[KlineData],
+    crosshair: Option<usize>,
+    view: ChartView,
+    inner: Rect,
+) -> Option<Rect> {
+    let style = app.config.chart_style_for(symbol);
+    let window = view.window(klines.len());
+    if window.is_empty() {
+        f.render_widget(Paragraph::new(tr("暂无K线数据")), inner);
+        return None;
     }
+    // 光标转换为可见窗口内的下标
+    let cursor = crosshair;
+    let crosshair = crosshair
+        .filter(|index| window.contains(index))
+        .map(|index| index - window.start);
 
-    // 计算价格范围
+    let theme = &app.theme;
+    let chart_config = &app.config.chart;
+    let overlays = overlay_series(chart_config, theme, klines, window.clone());
+    let levels = PriceLevels::new(app, symbol, &klines[window.clone()]);
+    // 平均K线依赖前一根的数值，在全部已加载K线上计算
+    let mut candles: Vec<Candle> = klines.iter().map(Candle::from).collect();
+    if style == ChartStyle::HeikinAshi {
+        candles = heikin_ashi(&candles);
+    }
+    let candles = &candles[window.clone()];
+
+    // 开启对比时价格图改为各交易对的涨跌幅折线
+    let comparison = comparison_series(app, symbol, &klines[window.clone()]);
+
+    // 价格轴宽度按最密的刻度（小数位最多）估算，各副图共用同样的绘图宽度以保持对齐
+    let (min_price, max_price) = match &comparison {
+        Some(series) => percent_range(series),
+        None => price_range(candles, &overlays),
+    };
+    let scale = PriceScale::new(min_price, max_price, comparison.is_none() && chart_config.log_scale);
+    let (_, decimals) = price_ticks(scale, inner.height as usize / 2);
+    let axis_labels: Vec<String> = match &comparison {
+        Some(_) => [min_price, max_price]
+            .iter()
+            .map(|percent| format!("{:+.*}%", decimals, percent))
+            .collect(),
+        None => [min_price, max_price]
+            .iter()
+            .map(|price| format!("{:.*}", decimals, price))
+            .chain([levels.last, levels.high_24h, levels.low_24h].iter().flatten().map(|p| levels.format(*p)))
+            .collect(),
+    };
+    let axis_width = axis_labels
+        .iter()
+        .map(|label| label.chars().count() as u16 + 1)
+        .max()
+        .unwrap_or(0)
+        .min(inner.width / 3);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(axis_width)])
+        .split(inner);
+    let (plot, axis) = (columns[0], columns[1]);
+    // 每列最多一根K线，放不下时按实际绘图宽度重新计算可见窗口
+    if window.len() > plot.width as usize && plot.width > 0 {
+        let view = ChartView {
+            max_visible: Some(plot.width as usize),
+            ..view
+        };
+        return draw_chart_panes(f, app, symbol, klines, cursor, view, inner);
+    }
+
+    // 高度足够时最后一行为时间轴
+    let time_axis_rows = u16::from(inner.height >= 8);
+
+    // 副图自上而下依次为成交量和各震荡指标，空间不足时从最下方开始舍弃
+    let mut sub_panes: Vec<(Option<Oscillator>, u16)> = Vec::new();
+    if chart_config.show_volume {
+        let rows = inner.height * chart_config.volume_height_percent.min(80) / 100;
+        // 至少需要标题行加两行柱状图
+        if rows >= 3 {
+            sub_panes.push((None, rows));
+        }
+    }
+    let oscillator_rows = chart_config.oscillator_height.max(4);
+    for oscillator in oscillator_series(chart_config, theme, klines, window.clone()) {
+        sub_panes.push((Some(oscillator), oscillator_rows));
+    }
+    // 价格区域至少保留 5 行，图表本身更矮时不显示副图
+    while sub_panes.iter().map(|(_, rows)| rows).sum::<u16>() + time_axis_rows + 5 > inner.height {
+        if sub_panes.pop().is_none() {
+            break;
+        }
+    }
+
+    let constraints: Vec<Constraint> = std::iter::once(Constraint::Min(0))
+        .chain(sub_panes.iter().map(|(_, rows)| Constraint::Length(*rows)))
+        .chain(std::iter::once(Constraint::Length(time_axis_rows)))
+        .collect();
+    let areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(plot);
+
+    let price_axis = Rect {
+        y: areas[0].y,
+        height: areas[0].height,
+        ..axis
+    };
+    match &comparison {
+        Some(series) => draw_comparison_chart(f, theme, series, scale, crosshair, areas[0], price_axis),
+        None => draw_candlestick_chart(
+            f,
+            theme,
+            candles,
+            style,
+            scale,
+            &overlays,
+            &levels,
+            crosshair,
+            areas[0],
+            price_axis,
+        ),
+    }
+    for ((pane, _), area) in sub_panes.iter().zip(areas.iter().skip(1)) {
+        match pane {
+            None => draw_volume_chart(f, theme, klines, window.clone(), crosshair, chart_config.volume_ma_period, *area),
+            Some(oscillator) => draw_oscillator(f, theme, oscillator, &levels, window.len(), crosshair, *area),
+        }
+    }
+    if time_axis_rows > 0 {
+        draw_time_axis(f, theme, &klines[window], areas[areas.len() - 1]);
+    }
+
+    Some(plot)
+}
+
+/// 价格图上的参考价位：最新价和24小时最高/最低价
+struct PriceLevels<'a> {
+    last: Option<f64>,
+    high_24h: Option<f64>,
+    low_24h: Option<f64>,
+    /// 交易对的价格精度，用于参考价位和指标数值的显示
+    filters: Option<&'a SymbolFilters>,
+}
+
+impl<'a> PriceLevels<'a> {
+    fn new(app: &'a App, symbol: &str, visible: &[KlineData]) -> Self {
+        let ticker = app.ticker_prices.get(symbol);
+        let parse = |value: &String| value.parse::<f64>().ok();
+        Self {
+            // 行情尚未加载时用最后一根K线的收盘价
+            last: ticker
+                .and_then(|t| parse(&t.price))
+                .or_else(|| visible.last().and_then(|k| parse(&k.close))),
+            high_24h: ticker.and_then(|t| parse(&t.high_24h)),
+            low_24h: ticker.and_then(|t| parse(&t.low_24h)),
+            filters: app.symbol_filters.get(symbol),
+        }
+    }
+
+    fn format(&self, price: f64) -> String {
+        format_price(price, self.filters)
+    }
+}
+
+/// 对比图中的一条涨跌幅折线
+struct ComparisonSeries {
+    symbol: String,
+    color: Color,
+    /// 与可见K线逐根对齐的涨跌幅（%），以窗口内第一根有效K线为 0%
+    values: Vec<Option<f64>>,
+}
+
+/// 选中交易对开启对比时，当前交易对及各对比交易对按开盘时间对齐的涨跌幅
+fn comparison_series(app: &App, symbol: &str, visible: &[KlineData]) -> Option<Vec<ComparisonSeries>> {
+    if app.selected_symbol.as_deref() != Some(symbol) {
+        return None;
+    }
+    let others: Vec<&String> = app
+        .config
+        .chart
+        .compare_symbols
+        .iter()
+        .filter(|other| *other != symbol)
+        .collect();
+    if others.is_empty() {
+        return None;
+    }
+
+    let times: Vec<i64> = visible.iter().map(|k| k.open_time).collect();
+    let closes = |klines: &[KlineData]| -> Vec<(i64, f64)> {
+        klines
+            .iter()
+            .map(|k| (k.open_time, k.close.parse::<f64>().unwrap_or(0.0)))
+            .collect()
+    };
+    let series = std::iter::once((symbol, visible))
+        .chain(others.iter().map(|other| {
+            let klines = app.comparison.get(*other).map_or(&[][..], Vec::as_slice);
+            (other.as_str(), klines)
+        }))
+        .zip(app.theme.palette.iter().cycle())
+        .map(|((symbol, klines), color)| ComparisonSeries {
+            symbol: symbol.to_string(),
+            color: *color,
+            values: indicators::rebased_percent(&times, &closes(klines)),
+        })
+        .collect();
+    Some(series)
+}
+
+/// 对比折线的涨跌幅范围，始终包含 0%
+fn percent_range(series: &[ComparisonSeries]) -> (f64, f64) {
+    series
+        .iter()
+        .flat_map(|s| s.values.iter().flatten())
+        .fold((0.0, 0.0), |(min, max): (f64, f64), v| (min.min(*v), max.max(*v)))
+}
+
+/// 可见K线及叠加指标的价格范围
+fn price_range(candles: &[Candle], overlays: &[Overlay]) -> (f64, f64) {
     let mut min_price = f64::MAX;
     let mut max_price = f64::MIN;
-    
-    for kline in klines {
-        let high = kline.high.parse::<f64>().unwrap_or(0.0);
-        let low = kline.low.parse::<f64>().unwrap_or(0.0);
-        min_price = min_price.min(low);
-        max_price = max_price.max(high);
+    for candle in candles {
+        min_price = min_price.min(candle.low);
+        max_price = max_price.max(candle.high);
+    }
+    for value in overlays.iter().flat_map(|o| o.lines.iter().flatten().flatten()) {
+        min_price = min_price.min(*value);
+        max_price = max_price.max(*value);
     }
+    (min_price, max_price)
+}
 
-    let price_range = max_price - min_price;
-    let chart_height = area.height.saturating_sub(2) as f64;
-    let chart_width = area.width.saturating_sub(2) as f64;
+/// 在价格区间内生成不超过 `max_ticks` 个整齐的刻度，同时返回标签需要的小数位数。
+/// 线性坐标的步长为 1、2、5 乘以 10 的整数次幂；对数坐标取 1、2、5 乘以 10 的整数次幂处的价格，
+/// 区间太窄放不下两个这样的刻度时退回线性刻度。
+fn price_ticks(scale: PriceScale, max_ticks: usize) -> (Vec<f64>, usize) {
+    let PriceScale { min, max, log } = scale;
+    if log && max_ticks > 0 {
+        if let Some(ticks) = log_ticks(min, max, max_ticks) {
+            let decimals = (-ticks[0].log10().floor()).max(0.0) as usize;
+            return (ticks, decimals);
+        }
+    }
 
+    let range = max - min;
+    if !range.is_finite() || range <= 0.0 || max_ticks == 0 {
+        return (Vec::new(), 2);
+    }
+    let raw_step = range / max_ticks as f64;
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|step| *step >= raw_step)
+        .unwrap_or(10.0 * magnitude);
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+
+    let mut ticks = Vec::new();
+    let mut tick = (min / step).ceil() * step;
+    while tick <= max + step * 1e-9 {
+        ticks.push(tick);
+        tick += step;
+    }
+    (ticks, decimals)
+}
+
+/// 对数坐标的刻度，先尝试 1、2、5 倍，放不下时只保留 10 的整数次幂并按间隔抽取。
+/// 范围内没有 10 的整数次幂时返回 `None`，由调用方退回线性刻度
+fn log_ticks(min: f64, max: f64, max_ticks: usize) -> Option<Vec<f64>> {
+    let decades = min.log10().floor() as i32..=max.log10().ceil() as i32;
+    let candidates = |mantissas: &[f64]| -> Vec<f64> {
+        decades
+            .clone()
+            .flat_map(|exponent| mantissas.iter().map(move |m| m * 10f64.powi(exponent)))
+            .filter(|tick| *tick >= min && *tick <= max)
+            .collect()
+    };
+
+    let ticks = candidates(&[1.0, 2.0, 5.0]);
+    if ticks.len() < 2 {
+        return None;
+    }
+    if ticks.len() <= max_ticks {
+        return Some(ticks);
+    }
+    let powers = candidates(&[1.0]);
+    if powers.is_empty() {
+        return None;
+    }
+    let stride = powers.len().div_ceil(max_ticks);
+    Some(powers.into_iter().step_by(stride).collect())
+}
+
+/// 底部时间轴：按可见范围选择时间格式，标签之间至少间隔两列
+fn draw_time_axis(f: &mut Frame, theme: &Theme, klines: &[KlineData], area: Rect) {
+    let first = klines[0].open_time;
+    let last = klines[klines.len() - 1].open_time;
+    let step = klines.get(1).map_or(0, |k| k.open_time - first);
+    const DAY: i64 = 24 * 60 * 60 * 1000;
+    let format = if step >= DAY {
+        DateFormat::Date
+    } else if step > 0 && step < 60 * 1000 {
+        DateFormat::TimeSeconds
+    } else if last - first < DAY {
+        DateFormat::Time
+    } else {
+        DateFormat::DayTime
+    };
+    let label = |open_time: i64| format_timestamp(open_time, format);
+
+    let style = Style::default().fg(theme.muted);
+    let mut next_free = 0;
     for (i, kline) in klines.iter().enumerate() {
-        let open = kline.open.parse::<f64>().unwrap_or(0.0);
-        let close = kline.close.parse::<f64>().unwrap_or(0.0);
-        let high = kline.high.parse::<f64>().unwrap_or(0.0);
-        let low = kline.low.parse::<f64>().unwrap_or(0.0);
-        
-        let is_green = close >= open;
-        let color = if is_green { Color::Green } else { Color::Red };
-        
-        let x = (i as f64 / klines.len() as f64 * chart_width) as u16;
-        let open_y = ((max_price - open) / price_range * chart_height) as u16;
-        let close_y = ((max_price - close) / price_range * chart_height) as u16;
-        let high_y = ((max_price - high) / price_range * chart_height) as u16;
-        let low_y = ((max_price - low) / price_range * chart_height) as u16;
-        
-        // 绘制K线
-        let candle_char = if is_green { "█" } else { "█" };
-        let wick_char = "│";
-        
-        // 绘制影线
-        for y in low_y..=high_y {
-            if y < area.height.saturating_sub(2) {
-                f.render_widget(
-                    Paragraph::new(wick_char).style(Style::default().fg(color)),
-                    Rect::new(area.x + x, area.y + y + 1, 1, 1),
-                );
+        let column = column_x(i, klines.len(), area.width);
+        let text = format!("┬{}", label(kline.open_time));
+        let width = text.chars().count() as u16;
+        if column >= next_free && column + width <= area.width {
+            f.render_widget(
+                Paragraph::new(text).style(style),
+                Rect::new(area.x + column, area.y, width, 1),
+            );
+            next_free = column + width + 2;
+        }
+    }
+}
+
+/// 叠加在价格图上的一组线（均线为一条，布林带为上中下三条）
+struct Overlay {
+    label: String,
+    color: Color,
+    /// 与可见K线逐根对齐的数值
+    lines: Vec<Vec<Option<f64>>>,
+}
+
+/// 根据配置在全部已加载K线上计算均线和布林带，再截取可见窗口，
+/// 这样窗口开头的K线也有完整的指标数值
+fn overlay_series(
+    chart_config: &ChartConfig,
+    theme: &Theme,
+    klines: &[KlineData],
+    window: std::ops::Range<usize>,
+) -> Vec<Overlay> {
+    let closes: Vec<f64> = klines
+        .iter()
+        .map(|k| k.close.parse::<f64>().unwrap_or(0.0))
+        .collect();
+    let mut overlays = Vec::new();
+
+    if chart_config.show_moving_averages {
+        for ma in &chart_config.moving_averages {
+            let values = match ma.kind {
+                MovingAverageKind::Sma => indicators::sma(&closes, ma.period),
+                MovingAverageKind::Ema => indicators::ema(&closes, ma.period),
+                MovingAverageKind::Wma => indicators::wma(&closes, ma.period),
+            };
+            overlays.push(Overlay {
+                label: format!("{}{}", ma.kind.label(), ma.period),
+                color: parse_color(&ma.color).unwrap_or(theme.text),
+                lines: vec![values[window.clone()].to_vec()],
+            });
+        }
+    }
+
+    if chart_config.show_bollinger {
+        let config = &chart_config.bollinger;
+        let bands = indicators::bollinger(&closes, config.period, config.std_dev);
+        let band = |pick: fn(&indicators::BollingerPoint) -> f64| {
+            bands[window.clone()].iter().map(|p| p.as_ref().map(pick)).collect()
+        };
+        overlays.push(Overlay {
+            label: format!("BOLL({},{})", config.period, config.std_dev),
+            color: parse_color(&config.color).unwrap_or(theme.muted),
+            lines: vec![band(|p| p.upper), band(|p| p.middle), band(|p| p.lower)],
+        });
+    }
+
+    overlays
+}
+
+/// 按图表样式绘制主图，并叠加坐标轴、参考线和指标
+#[allow(clippy::too_many_arguments)]
+fn draw_candlestick_chart(
+    f: &mut Frame,
+    theme: &Theme,
+    candles: &[Candle],
+    style: ChartStyle,
+    scale: PriceScale,
+    overlays: &[Overlay],
+    levels: &PriceLevels,
+    crosshair: Option<usize>,
+    area: Rect,
+    axis: Rect,
+) {
+    // 有叠加指标时首行显示图例，价格轴与绘图区域一起下移
+    let (area, axis) = if overlays.is_empty() || area.height < 4 {
+        (area, axis)
+    } else {
+        draw_overlay_legend(f, overlays, levels, crosshair.unwrap_or(candles.len() - 1), Rect { height: 1, ..area });
+        let below_legend = |rect: Rect| Rect {
+            y: rect.y + 1,
+            height: rect.height - 1,
+            ..rect
+        };
+        (below_legend(area), below_legend(axis))
+    };
+
+    // 价格范围包含叠加指标以免超出图表
+    let price_y = |price: f64| scale.row(price, area.height);
+    let in_range = |price: f64| price >= scale.min && price <= scale.max;
+
+    // 价格轴刻度
+    let (ticks, decimals) = price_ticks(scale, area.height as usize / 2);
+    // 刻度按步长取小数位，参考价位显示完整价格
+    let axis_label = |f: &mut Frame, price: f64, text: String, style: Style| {
+        let y = price_y(price);
+        if y < axis.height && axis.width > 0 {
+            let text = format!("─{}", text);
+            f.render_widget(Paragraph::new(text).style(style), Rect { y: axis.y + y, height: 1, ..axis });
+        }
+    };
+    for tick in ticks {
+        axis_label(f, tick, format!("{:.*}", decimals, tick), Style::default().fg(theme.muted));
+    }
+
+    // 24小时最高/最低价参考线
+    let reference_style = Style::default().fg(theme.primary);
+    for (price, name) in [(levels.high_24h, tr("24h高")), (levels.low_24h, tr("24h低"))] {
+        if let Some(price) = price.filter(|p| in_range(*p)) {
+            let y = area.y + price_y(price);
+            for x in 0..area.width {
+                draw_cell(f, area.x + x, y, "┄", reference_style);
             }
+            f.render_widget(Paragraph::new(name).style(reference_style), Rect::new(area.x, y, area.width.min(6), 1));
+            axis_label(f, price, levels.format(price), reference_style);
         }
-        
-        // 绘制实体
-        let start_y = open_y.min(close_y);
-        let end_y = open_y.max(close_y);
-        for y in start_y..=end_y {
-            if y < area.height.saturating_sub(2) {
-                f.render_widget(
-                    Paragraph::new(candle_char).style(Style::default().fg(color)),
-                    Rect::new(area.x + x, area.y + y + 1, 1, 1),
-                );
+    }
+
+    // 最新价标记线
+    if let Some(price) = levels.last.filter(|p| in_range(*p)) {
+        let y = area.y + price_y(price);
+        for x in 0..area.width {
+            draw_cell(f, area.x + x, y, "╌", Style::default().fg(theme.secondary));
+        }
+        // 背景色为终端默认时用反色显示，保证标签可读
+        let last_style = match theme.background {
+            Color::Reset => Style::default().fg(theme.secondary).add_modifier(Modifier::REVERSED),
+            background => Style::default().fg(background).bg(theme.secondary),
+        };
+        axis_label(f, price, levels.format(price), last_style);
+    }
+
+    // 先绘制十字光标，K线绘制在其上方
+    if let Some(index) = crosshair {
+        let crosshair_style = Style::default().fg(theme.muted);
+        let close = candles[index].close;
+        let cursor_x = column_x(index, candles.len(), area.width);
+        let cursor_y = price_y(close).min(area.height.saturating_sub(1));
+        for y in 0..area.height {
+            draw_cell(f, area.x + cursor_x, area.y + y, "│", crosshair_style);
+        }
+        for x in 0..area.width {
+            let symbol = if x == cursor_x { "┼" } else { "─" };
+            draw_cell(f, area.x + x, area.y + cursor_y, symbol, crosshair_style);
+        }
+    }
+
+    match style {
+        ChartStyle::Candles | ChartStyle::OhlcBars | ChartStyle::HeikinAshi => f.render_widget(
+            CandleChart::new(candles)
+                .scale(scale)
+                .up_style(Style::default().fg(theme.up))
+                .down_style(Style::default().fg(theme.down))
+                .highlight(crosshair)
+                .ohlc_bars(style == ChartStyle::OhlcBars),
+            area,
+        ),
+        ChartStyle::Line | ChartStyle::Area => {
+            let closes: Vec<Option<f64>> = candles.iter().map(|candle| Some(candle.close)).collect();
+            let fill = (style == ChartStyle::Area).then_some(theme.fill);
+            f.render_widget(
+                PriceLine::new(&closes, scale)
+                    .color(theme.palette[3])
+                    .fill(fill),
+                area,
+            );
+        }
+    }
+
+    // 叠加指标画在K线之上
+    for overlay in overlays {
+        let style = Style::default().fg(overlay.color);
+        for line in &overlay.lines {
+            for (i, value) in line.iter().enumerate() {
+                if let Some(value) = value {
+                    let y = price_y(*value);
+                    if y < area.height {
+                        draw_cell(f, area.x + column_x(i, candles.len(), area.width), area.y + y, "•", style);
+                    }
+                }
             }
         }
     }
+}
 
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .title(format!("K线图 - {} (5分钟周期)", symbol));
-    f.render_widget(block, area);
-} 
\ No newline at end of file
+/// 对比图：各交易对的涨跌幅折线，首行图例显示光标所在（或最新）K线处的涨跌幅
+fn draw_comparison_chart(
+    f: &mut Frame,
+    theme: &Theme,
+    series: &[ComparisonSeries],
+    scale: PriceScale,
+    crosshair: Option<usize>,
+    area: Rect,
+    axis: Rect,
+) {
+    let count = series[0].values.len();
+    let index = crosshair.unwrap_or(count - 1);
+    let legend: Vec<Span> = series
+        .iter()
+        .map(|s| {
+            let value = s.values.get(index).copied().flatten();
+            let text = value.map_or("-".to_string(), |v| format!("{:+.2}%", v));
+            Span::styled(format!("{}: {}  ", s.symbol, text), Style::default().fg(s.color))
+        })
+        .collect();
+    f.render_widget(Paragraph::new(Line::from(legend)), Rect { height: 1, ..area });
+    let below_legend = |rect: Rect| Rect {
+        y: rect.y + 1,
+        height: rect.height.saturating_sub(1),
+        ..rect
+    };
+    let (area, axis) = (below_legend(area), below_legend(axis));
+
+    let guide_style = Style::default().fg(theme.muted);
+    let (ticks, decimals) = price_ticks(scale, area.height as usize / 2);
+    for tick in ticks {
+        let y = axis.y + scale.row(tick, axis.height);
+        if axis.width > 0 {
+            let text = format!("─{:+.*}%", decimals, tick);
+            f.render_widget(Paragraph::new(text).style(guide_style), Rect { y, height: 1, ..axis });
+        }
+    }
+    let zero_y = area.y + scale.row(0.0, area.height);
+    for x in 0..area.width {
+        draw_cell(f, area.x + x, zero_y, "┈", guide_style);
+    }
+    if let Some(index) = crosshair {
+        let x = area.x + column_x(index, count, area.width);
+        for y in 0..area.height {
+            draw_cell(f, x, area.y + y, "│", guide_style);
+        }
+    }
+
+    for s in series {
+        // 对比交易对在窗口开头缺少数据时，第一根有效K线之前留空
+        f.render_widget(PriceLine::new(&s.values, scale).color(s.color), area);
+    }
+}
+
+/// 图例：每个叠加指标在光标所在（或最新）K线处的数值
+fn draw_overlay_legend(f: &mut Frame, overlays: &[Overlay], levels: &PriceLevels, index: usize, area: Rect) {
+    let mut spans = Vec::new();
+    for overlay in overlays {
+        let values: Vec<String> = overlay
+            .lines
+            .iter()
+            .map(|line| match line.get(index).copied().flatten() {
+                Some(value) => levels.format(value),
+                None => "-".to_string(),
+            })
+            .collect();
+        spans.push(Span::styled(
+            format!("{}: {}  ", overlay.label, values.join("/")),
+            Style::default().fg(overlay.color),
+        ));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// 价格图下方的震荡指标副图，与K线共用横轴
+struct Oscillator {
+    title: String,
+    /// 固定的纵轴范围，`None` 时按可见数据自动计算
+    range: Option<(f64, f64)>,
+    /// 水平参考线（超买超卖线、零轴）
+    guides: Vec<f64>,
+    /// 名称、颜色以及与可见K线逐根对齐的数值
+    lines: Vec<(&'static str, Color, Vec<Option<f64>>)>,
+    /// MACD 柱状图
+    histogram: Option<Vec<Option<f64>>>,
+    /// 数值以价格为单位（MACD），按交易对的价格精度显示，否则保留两位小数
+    priced: bool,
+}
+
+/// 在全部已加载K线上计算启用的震荡指标，再截取可见窗口
+fn oscillator_series(
+    chart_config: &ChartConfig,
+    theme: &Theme,
+    klines: &[KlineData],
+    window: std::ops::Range<usize>,
+) -> Vec<Oscillator> {
+    let parse = |field: fn(&KlineData) -> &String| -> Vec<f64> {
+        klines.iter().map(|k| field(k).parse::<f64>().unwrap_or(0.0)).collect()
+    };
+    let closes = parse(|k| &k.close);
+    let mut oscillators = Vec::new();
+
+    if chart_config.show_rsi {
+        let config = &chart_config.rsi;
+        let values = indicators::rsi(&closes, config.period);
+        oscillators.push(Oscillator {
+            title: format!("RSI({})", config.period),
+            range: Some((0.0, 100.0)),
+            guides: vec![config.overbought, config.oversold],
+            lines: vec![("RSI", theme.palette[2], values[window.clone()].to_vec())],
+            histogram: None,
+            priced: false,
+        });
+    }
+
+    if chart_config.show_macd {
+        let config = &chart_config.macd;
+        let points = indicators::macd(&closes, config.fast, config.slow, config.signal);
+        let visible = &points[window.clone()];
+        let pick = |f: fn(&indicators::MacdPoint) -> f64| visible.iter().map(|p| p.as_ref().map(f)).collect();
+        oscillators.push(Oscillator {
+            title: format!("MACD({},{},{})", config.fast, config.slow, config.signal),
+            range: None,
+            guides: vec![0.0],
+            lines: vec![
+                ("DIF", theme.palette[0], pick(|p| p.macd)),
+                ("DEA", theme.palette[1], pick(|p| p.signal)),
+            ],
+            histogram: Some(pick(|p| p.histogram)),
+            priced: true,
+        });
+    }
+
+    if chart_config.show_stochastic {
+        let config = &chart_config.stochastic;
+        let highs = parse(|k| &k.high);
+        let lows = parse(|k| &k.low);
+        let points = indicators::stochastic(
+            &highs,
+            &lows,
+            &closes,
+            config.k_period,
+            config.k_smooth,
+            config.d_period,
+        );
+        let visible = &points[window];
+        let pick = |f: fn(&indicators::StochasticPoint) -> f64| visible.iter().map(|p| p.as_ref().map(f)).collect();
+        oscillators.push(Oscillator {
+            title: format!("STOCH({},{},{})", config.k_period, config.k_smooth, config.d_period),
+            range: Some((0.0, 100.0)),
+            guides: vec![config.overbought, config.oversold],
+            lines: vec![("K", theme.palette[0], pick(|p| p.k)), ("D", theme.palette[1], pick(|p| p.d))],
+            histogram: None,
+            priced: false,
+        });
+    }
+
+    oscillators
+}
+
+/// 绘制震荡指标副图：首行为标题和光标所在（或最新）K线处的数值
+fn draw_oscillator(
+    f: &mut Frame,
+    theme: &Theme,
+    oscillator: &Oscillator,
+    levels: &PriceLevels,
+    count: usize,
+    crosshair: Option<usize>,
+    area: Rect,
+) {
+    let index = crosshair.unwrap_or(count - 1);
+    let mut spans = vec![Span::styled(
+        format!("─ {} ", oscillator.title),
+        Style::default().fg(theme.muted),
+    )];
+    let format = |value: f64| {
+        if oscillator.priced {
+            levels.format(value)
+        } else {
+            format_number(value, Some(2))
+        }
+    };
+    for (name, color, values) in &oscillator.lines {
+        let value = values.get(index).copied().flatten();
+        spans.push(Span::styled(
+            format!("{}: {}  ", name, value.map_or("-".to_string(), format)),
+            Style::default().fg(*color),
+        ));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), Rect { height: 1, ..area });
+
+    let plot = Rect {
+        y: area.y + 1,
+        height: area.height - 1,
+        ..area
+    };
+    let (min, max) = oscillator.range.unwrap_or_else(|| {
+        let values = oscillator
+            .lines
+            .iter()
+            .flat_map(|(_, _, values)| values.iter())
+            .chain(oscillator.histogram.iter().flatten())
+            .flatten()
+            .chain(oscillator.guides.iter());
+        values.fold((f64::MAX, f64::MIN), |(min, max), v| (min.min(*v), max.max(*v)))
+    });
+    if max <= min {
+        return;
+    }
+    let value_y = |value: f64| {
+        let ratio = ((max - value) / (max - min)).clamp(0.0, 1.0);
+        plot.y + (ratio * plot.height.saturating_sub(1) as f64).round() as u16
+    };
+    let guide_style = Style::default().fg(theme.muted);
+
+    for guide in &oscillator.guides {
+        let y = value_y(*guide);
+        for x in 0..plot.width {
+            draw_cell(f, plot.x + x, y, "┈", guide_style);
+        }
+    }
+    if let Some(index) = crosshair {
+        let x = plot.x + column_x(index, count, plot.width);
+        for y in 0..plot.height {
+            draw_cell(f, x, plot.y + y, "│", guide_style);
+        }
+    }
+
+    if let Some(histogram) = &oscillator.histogram {
+        let zero_y = value_y(0.0);
+        for (i, value) in histogram.iter().enumerate() {
+            let Some(value) = value else { continue };
+            let x = plot.x + column_x(i, count, plot.width);
+            let y = value_y(*value);
+            let style = Style::default().fg(theme.change(*value >= 0.0));
+            for row in y.min(zero_y)..=y.max(zero_y) {
+                draw_cell(f, x, row, "▐", style);
+            }
+        }
+    }
+
+    for (_, color, values) in &oscillator.lines {
+        for (i, value) in values.iter().enumerate() {
+            if let Some(value) = value {
+                let x = plot.x + column_x(i, count, plot.width);
+                draw_cell(f, x, value_y(*value), "•", Style::default().fg(*color));
+            }
+        }
+    }
+}
+
+/// 成交量柱状图：首行为标题，柱子与K线逐列对齐，颜色与K线涨跌一致。
+/// `klines` 为全部已加载的K线，以便均线在可见窗口开头也有数值。
+fn draw_volume_chart(
+    f: &mut Frame,
+    theme: &Theme,
+    klines: &[KlineData],
+    window: std::ops::Range<usize>,
+    crosshair: Option<usize>,
+    ma_period: usize,
+    area: Rect,
+) {
+    const EIGHTHS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+    let volumes: Vec<f64> = klines
+        .iter()
+        .map(|k| k.volume.parse::<f64>().unwrap_or(0.0))
+        .collect();
+    let moving_average = if ma_period > 0 {
+        indicators::sma(&volumes, ma_period)
+    } else {
+        Vec::new()
+    };
+
+    let visible = &klines[window.clone()];
+    let visible_volumes = &volumes[window.clone()];
+    let visible_ma = moving_average.get(window.clone()).unwrap_or(&[]);
+    let max_volume = visible_volumes
+        .iter()
+        .chain(visible_ma.iter().flatten())
+        .fold(0.0_f64, |max, v| max.max(*v));
+
+    // 标题行：光标所在或最新K线的成交量及均线
+    let label_index = crosshair.unwrap_or(visible.len() - 1);
+    let mut label = trf("─ 成交量: {}", &[&format_compact(visible_volumes[label_index])]);
+    if let Some(Some(ma)) = visible_ma.get(label_index) {
+        label.push_str(&format!("  MA{}: {}", ma_period, format_compact(*ma)));
+    }
+    let separator = Paragraph::new(format!("{} {}", label, "─".repeat(area.width as usize)))
+        .style(Style::default().fg(theme.muted));
+    f.render_widget(separator, Rect { height: 1, ..area });
+
+    let bars = Rect {
+        y: area.y + 1,
+        height: area.height - 1,
+        ..area
+    };
+    if let Some(index) = crosshair {
+        let x = column_x(index, visible.len(), bars.width);
+        for y in 0..bars.height {
+            draw_cell(f, bars.x + x, bars.y + y, "│", Style::default().fg(theme.muted));
+        }
+    }
+    if max_volume <= 0.0 {
+        return;
+    }
+
+    // 以 1/8 行为单位计算柱高
+    let to_eighths = |volume: f64| (volume / max_volume * bars.height as f64 * 8.0).round() as u16;
+    for (i, kline) in visible.iter().enumerate() {
+        let x = bars.x + column_x(i, visible.len(), bars.width);
+        let is_green = kline.close.parse::<f64>().unwrap_or(0.0) >= kline.open.parse::<f64>().unwrap_or(0.0);
+        let style = Style::default().fg(theme.change(is_green));
+
+        let eighths = to_eighths(visible_volumes[i]);
+        for row in 0..bars.height {
+            let filled = eighths.saturating_sub(row * 8).min(8);
+            if filled > 0 {
+                let y = bars.y + bars.height - 1 - row;
+                draw_cell(f, x, y, EIGHTHS[filled as usize - 1], style);
+            }
+        }
+
+        // 均线点画在柱子之上
+        if let Some(Some(ma)) = visible_ma.get(i) {
+            let row = (to_eighths(*ma) / 8).min(bars.height - 1);
+            draw_cell(f, x, bars.y + bars.height - 1 - row, "•", Style::default().fg(theme.palette[0]));
+        }
+    }
+}
+/// 在单个单元格中绘制一个字符
+fn draw_cell(f: &mut Frame, x: u16, y: u16, symbol: &str, style: Style) {
+    let buffer = f.buffer_mut();
+    if buffer.area.contains(ratatui::layout::Position { x, y }) {
+        buffer.get_mut(x, y).set_symbol(symbol).set_style(style);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_ticks_prefer_one_two_five() {
+        assert_eq!(log_ticks(80.0, 600.0, 10), Some(vec![100.0, 200.0, 500.0]));
+        assert_eq!(log_ticks(0.015, 0.06, 10), Some(vec![0.02, 0.05]));
+    }
+
+    #[test]
+    fn log_ticks_fall_back_to_powers_of_ten() {
+        assert_eq!(log_ticks(1.0, 100_000.0, 3), Some(vec![1.0, 100.0, 10_000.0]));
+        // 没有 10 的整数次幂，也放不下 1、2、5 倍的刻度
+        assert_eq!(log_ticks(15_000.0, 60_000.0, 1), None);
+        // 只有一个候选刻度
+        assert_eq!(log_ticks(15_000.0, 30_000.0, 5), None);
+    }
+
+    #[test]
+    fn price_ticks_fall_back_to_linear_scale() {
+        let scale = PriceScale::new(15_000.0, 60_000.0, true);
+        for max_ticks in 0..4 {
+            let (ticks, _) = price_ticks(scale, max_ticks);
+            assert!(ticks.len() <= max_ticks.max(1) + 1, "{} {:?}", max_ticks, ticks);
+            assert!(ticks.iter().all(|tick| (15_000.0..=60_000.0).contains(tick)));
+        }
+        let (ticks, decimals) = price_ticks(PriceScale::new(15_000.0, 60_000.0, true), 1);
+        assert_eq!((ticks, decimals), (vec![50_000.0], 0));
+    }
+
+    #[test]
+    fn price_ticks_use_round_linear_steps() {
+        let (ticks, decimals) = price_ticks(PriceScale::new(101.3, 104.9, false), 4);
+        assert_eq!(ticks, vec![102.0, 103.0, 104.0]);
+        assert_eq!(decimals, 0);
+
+        let (ticks, decimals) = price_ticks(PriceScale::new(0.0121, 0.0139, false), 4);
+        assert_eq!(ticks.len(), 3);
+        assert!((ticks[0] - 0.0125).abs() < 1e-12);
+        assert_eq!(decimals, 4);
+
+        let (ticks, decimals) = price_ticks(PriceScale::new(0.004, 0.4, true), 3);
+        assert_eq!((ticks, decimals), (vec![0.01, 0.1], 2));
+
+        assert!(price_ticks(PriceScale::new(5.0, 5.0, false), 4).0.is_empty());
+    }
+}